@@ -0,0 +1,89 @@
+// Times building the part graph of large synthetic schematics, which runs one span index query per
+// symbol. Run with `cargo bench --bench spatial`
+use aoc_2023::{
+    engine::Schematic,
+    generator::Generator,
+    geometry::{Bounds, Vec2},
+    spatial::SpanIndex,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    collections::HashMap,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+fn time<R, F: FnMut() -> R>(name: &str, iterations: u32, mut f: F) {
+    let mut best = Duration::MAX;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        black_box(f());
+        best = best.min(start.elapsed());
+    }
+    println!("{name:<40} {best:>12.3?}");
+}
+
+fn main() {
+    for (size, max_len) in [(200, 3), (1000, 3), (1000, 15), (2000, 3), (2000, 19)] {
+        let generated = Generator::new(size, size)
+            .max_digits(max_len)
+            .digit_density(0.3)
+            .symbol_density(0.3)
+            .seed(0x5eed)
+            .generate();
+        time(
+            &format!("parse {size}x{size}, numbers up to {max_len}"),
+            5,
+            || Schematic::parse(generated.lines()).unwrap(),
+        );
+    }
+
+    for max_len in [6, 60] {
+        query_spans(max_len);
+    }
+}
+
+// 100,000 3x3 queries over a 4000x1000 area packed with spans. Compares the index with looking up
+// every possible start in a map, as the schematic used to
+fn query_spans(max_len: u64) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut index = SpanIndex::new();
+    let mut starts = HashMap::new();
+    for y in 0..1000 {
+        let mut x = 0;
+        while x < 4000 {
+            let len = rng.random_range(1..=max_len);
+            index.insert(Vec2::new(x, y), len, ());
+            starts.insert(Vec2::new(x, y), len);
+            x += len as i64 + 1;
+        }
+    }
+    let queries: Vec<_> = (0..100_000)
+        .map(|_| {
+            let pos = Vec2::new(rng.random_range(0..4000), rng.random_range(0..1000));
+            Bounds::new(pos - Vec2::new(1, 1), pos + Vec2::new(1, 1))
+        })
+        .collect();
+    time(&format!("index, spans up to {max_len}"), 5, || {
+        queries
+            .iter()
+            .map(|bounds| index.query(*bounds).count())
+            .sum::<usize>()
+    });
+    time(&format!("map scan, spans up to {max_len}"), 5, || {
+        queries
+            .iter()
+            .map(|bounds| {
+                bounds
+                    .cells()
+                    .flat_map(|pos| (0..max_len as i64).map(move |dx| pos - Vec2::new(dx, 0)))
+                    .filter(|start| {
+                        starts
+                            .get(start)
+                            .is_some_and(|len| start.x + *len as i64 > bounds.min.x)
+                    })
+                    .count()
+            })
+            .sum::<usize>()
+    });
+}
@@ -0,0 +1,106 @@
+use aoc_2023::{
+    cache::{Answers, InputCache},
+    scaffold::Scaffold,
+};
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::Result;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+struct Cli {
+    /// Directory holding cached puzzle inputs. Defaults to the user cache directory
+    #[clap(long, env = "AOC_INPUT_DIR", global = true)]
+    input_dir: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Manage the local puzzle input cache
+    #[clap(subcommand)]
+    Inputs(InputsCommand),
+    /// Generate the module, binary and tests for a new day
+    New {
+        #[clap(long)]
+        day: u32,
+        /// Name of the library module. Defaults to dayNN
+        #[clap(long)]
+        name: Option<String>,
+        /// Root of the crate to add the day to
+        #[clap(long, default_value = ".")]
+        root: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum InputsCommand {
+    /// Show which days have cached inputs, examples and expected answers
+    List,
+    /// Print where the input for a day is (or would be) cached
+    Path {
+        #[clap(long)]
+        day: u32,
+        #[clap(long)]
+        example: bool,
+    },
+    /// Copy an input file into the cache
+    Add {
+        #[clap(long)]
+        day: u32,
+        file: PathBuf,
+        /// Store the file as the example input for the day
+        #[clap(long)]
+        example: bool,
+        /// Expected part 1 answer for the example
+        #[clap(long, requires = "example")]
+        part1: Option<u64>,
+        /// Expected part 2 answer for the example
+        #[clap(long, requires = "example")]
+        part2: Option<u64>,
+    },
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let cli = Cli::parse();
+    let cache = InputCache::new(cli.input_dir);
+
+    match cli.command {
+        Command::Inputs(InputsCommand::List) => {
+            println!("Inputs in {}", cache.dir().display());
+            for day in cache.list() {
+                println!("{day}");
+            }
+        }
+        Command::Inputs(InputsCommand::Path { day, example }) => {
+            println!("{}", cache.path(day, example).display());
+        }
+        Command::Inputs(InputsCommand::Add {
+            day,
+            file,
+            example,
+            part1,
+            part2,
+        }) => {
+            let path = if example {
+                let answers: Answers = [(1, part1), (2, part2)]
+                    .into_iter()
+                    .filter_map(|(part, value)| Some((part, value?)))
+                    .collect();
+                cache.add_example(day, &file, &answers)?
+            } else {
+                cache.add_input(day, &file)?
+            };
+            println!("Added {}", path.display());
+        }
+        Command::New { day, name, root } => {
+            for path in Scaffold::new(&root, day, name)?.generate()? {
+                println!("Wrote {}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,76 +1,71 @@
-use aoc_2023::{trie::Trie, Opt};
+use aoc_2023::{report::Report, trie::Trie, Opt};
 use clap::Parser;
 use color_eyre::eyre::Result;
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader, Read},
-};
+use std::collections::HashMap;
 
 fn main() -> Result<()> {
     color_eyre::install()?;
     let opt = Opt::parse();
+    let mut report = Report::new(1);
 
-    let digits = HashMap::from_iter(
-        [
-            ("0", 0u32),
-            ("1", 1),
-            ("2", 2),
-            ("3", 3),
-            ("4", 4),
-            ("5", 5),
-            ("6", 6),
-            ("7", 7),
-            ("8", 8),
-            ("9", 9),
-            ("zero", 0),
-            ("one", 1),
-            ("two", 2),
-            ("three", 3),
-            ("four", 4),
-            ("five", 5),
-            ("six", 6),
-            ("seven", 7),
-            ("eight", 8),
-            ("nine", 9),
-        ]
-        .into_iter(),
-    );
-    let reverse_digits = HashMap::from_iter(
-        [
-            ("0", 0u32),
-            ("1", 1),
-            ("2", 2),
-            ("3", 3),
-            ("4", 4),
-            ("5", 5),
-            ("6", 6),
-            ("7", 7),
-            ("8", 8),
-            ("9", 9),
-            ("orez", 0),
-            ("eno", 1),
-            ("owt", 2),
-            ("eerht", 3),
-            ("ruof", 4),
-            ("evif", 5),
-            ("xis", 6),
-            ("neves", 7),
-            ("thgie", 8),
-            ("enin", 9),
-        ]
-        .into_iter(),
-    );
+    let digits = HashMap::from_iter([
+        ("0", 0u32),
+        ("1", 1),
+        ("2", 2),
+        ("3", 3),
+        ("4", 4),
+        ("5", 5),
+        ("6", 6),
+        ("7", 7),
+        ("8", 8),
+        ("9", 9),
+        ("zero", 0),
+        ("one", 1),
+        ("two", 2),
+        ("three", 3),
+        ("four", 4),
+        ("five", 5),
+        ("six", 6),
+        ("seven", 7),
+        ("eight", 8),
+        ("nine", 9),
+    ]);
+    let reverse_digits = HashMap::from_iter([
+        ("0", 0u32),
+        ("1", 1),
+        ("2", 2),
+        ("3", 3),
+        ("4", 4),
+        ("5", 5),
+        ("6", 6),
+        ("7", 7),
+        ("8", 8),
+        ("9", 9),
+        ("orez", 0),
+        ("eno", 1),
+        ("owt", 2),
+        ("eerht", 3),
+        ("ruof", 4),
+        ("evif", 5),
+        ("xis", 6),
+        ("neves", 7),
+        ("thgie", 8),
+        ("enin", 9),
+    ]);
 
-    let numbers = if let Some(file_name) = opt.file_name {
-        let file = File::open(file_name)?;
-        get_line_numbers(file, &digits, &reverse_digits)?
-    } else {
-        get_line_numbers(std::io::stdin(), &digits, &reverse_digits)?
-    };
+    let numbers = get_line_numbers(
+        aoc_2023::lines(&opt)?,
+        &digits,
+        &reverse_digits,
+        &mut report,
+    )?;
 
     let sum: u32 = numbers.into_iter().sum();
-    println!("{sum}");
+    report.answer(2, "Calibration value sum", sum.into());
+    if let Some(expected) = opt.expected_answers()? {
+        report.check(&expected);
+    }
+    report.print(opt.format);
 
     Ok(())
 }
@@ -115,35 +110,32 @@ fn get_first_digit<I: Iterator<Item = char> + Clone>(
 }
 
 // Returns the first and last digit all the lines provided
-pub fn get_line_numbers<R: Read>(
-    r: R,
+pub fn get_line_numbers<I: Iterator<Item = std::io::Result<String>>>(
+    lines: I,
     fwd_digit_map: &HashMap<&'static str, u32>,
     bwd_digit_map: &HashMap<&'static str, u32>,
+    report: &mut Report,
 ) -> std::io::Result<Vec<u32>> {
     let fwd_trie = get_trie(fwd_digit_map);
     let bwd_trie = get_trie(bwd_digit_map);
 
-    let mut reader = BufReader::new(r);
     let mut numbers = Vec::new();
 
-    loop {
-        let mut line = String::new();
-        if reader.read_line(&mut line)? == 0 {
-            break;
-        }
+    for (line_idx, line) in lines.enumerate() {
+        let line = line?;
+        let line_idx = line_idx + 1;
 
-        let first_digit = if let Some(d) = get_first_digit(line.chars(), &fwd_digit_map, &fwd_trie)
-        {
+        let first_digit = if let Some(d) = get_first_digit(line.chars(), fwd_digit_map, &fwd_trie) {
             d
         } else {
-            println!("line has no digits");
+            report.warn(format!("line {line_idx} has no digits"));
             continue;
         };
         let last_digit =
-            if let Some(d) = get_first_digit(line.chars().rev(), &bwd_digit_map, &bwd_trie) {
+            if let Some(d) = get_first_digit(line.chars().rev(), bwd_digit_map, &bwd_trie) {
                 d
             } else {
-                println!("line has no digits");
+                report.warn(format!("line {line_idx} has no digits"));
                 continue;
             };
 
@@ -154,5 +146,5 @@ pub fn get_line_numbers<R: Read>(
 }
 
 fn get_trie(digit_map: &HashMap<&'static str, u32>) -> Trie {
-    Trie::new(digit_map.iter().map(|(s, _)| *s))
+    Trie::new(digit_map.keys().copied())
 }
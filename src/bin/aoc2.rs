@@ -1,22 +1,38 @@
-use aoc_2023::{block_game::Game, Opt};
+use aoc_2023::{block_game::Game, report::Report, Opt};
 use clap::Parser;
 use color_eyre::eyre::Result;
 
+#[derive(Parser, Debug)]
+struct Args {
+    #[clap(flatten)]
+    opt: Opt,
+
+    /// Write the parsed games to this file as JSON
+    #[cfg(feature = "serde")]
+    #[clap(long)]
+    export_json: Option<std::path::PathBuf>,
+}
+
 fn main() -> Result<()> {
     color_eyre::install()?;
-    let opt = Opt::parse();
-    let lines = aoc_2023::read_lines(&opt)?;
+    let args = Args::parse();
+    let opt = &args.opt;
+    let mut report = Report::new(2);
 
-    let games = lines
-        .into_iter()
-        .map(|line| Game::parse(&line))
+    let games = aoc_2023::lines(opt)?
+        .map(|line| Game::parse(&line?))
         .collect::<Result<Vec<_>>>()?;
+    #[cfg(feature = "serde")]
+    if let Some(path) = &args.export_json {
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        serde_json::to_writer_pretty(file, &games)?;
+    }
 
     let game_ids_total: u64 = games
         .iter()
         .filter_map(|game| {
             if game.possible(12, 13, 14) {
-                println!("Game {} is possible", game.game_id);
+                report.detail(format!("Game {} is possible", game.game_id));
                 Some(game.game_id)
             } else {
                 None
@@ -32,8 +48,12 @@ fn main() -> Result<()> {
         })
         .sum();
 
-    println!("Valid game ID sum: {}", game_ids_total);
-    println!("Game power sum: {}", power_sum);
+    report.answer(1, "Valid game ID sum", game_ids_total);
+    report.answer(2, "Game power sum", power_sum);
+    if let Some(expected) = opt.expected_answers()? {
+        report.check(&expected);
+    }
+    report.print(opt.format);
 
     Ok(())
 }
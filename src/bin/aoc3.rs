@@ -1,25 +1,132 @@
-use aoc_2023::{engine::Schematic, Opt};
+use aoc_2023::{
+    engine::{Schematic, SchematicConfig},
+    export::Picture,
+    render::{render, RenderStyle},
+    report::Report,
+    topology::Topology,
+    Opt,
+};
 use clap::Parser;
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Result};
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[clap(flatten)]
+    opt: Opt,
+
+    /// Treat every character other than digits and the background as a symbol
+    #[clap(long)]
+    any_symbol: bool,
+
+    /// Character used for empty cells
+    #[clap(long, default_value_t = '.')]
+    background: char,
+
+    /// What lies past the edges of the schematic when looking for adjacent symbols
+    #[clap(long, value_enum, default_value_t)]
+    topology: Topology,
+
+    /// Redraw the schematic with parts and gears highlighted
+    #[clap(long, value_enum, num_args = 0..=1, default_missing_value = "ansi")]
+    render: Option<RenderStyle>,
+
+    /// Write a picture of the schematic to this SVG file
+    #[clap(long)]
+    svg: Option<PathBuf>,
+
+    /// Write a picture of the schematic to this PNG file
+    #[clap(long)]
+    png: Option<PathBuf>,
+
+    /// Compare the schematic with the one in this file and show what changed
+    #[clap(long)]
+    diff: Option<PathBuf>,
+
+    /// Write the parsed schematic to this file as JSON
+    #[cfg(feature = "serde")]
+    #[clap(long)]
+    export_json: Option<PathBuf>,
+}
 
 fn main() -> Result<()> {
     color_eyre::install()?;
-    let opt = Opt::parse();
-    let lines = aoc_2023::read_lines(&opt)?;
+    let args = Args::parse();
+    let opt = &args.opt;
+    let mut report = Report::new(3);
+
+    let config = if args.any_symbol {
+        SchematicConfig::any_symbol(args.background)
+    } else {
+        SchematicConfig {
+            background: args.background,
+            ..Default::default()
+        }
+    }
+    .topology(args.topology);
+    // The lines are read as they are parsed, and the first read error stops both
+    let mut read_error = None;
+    let lines = aoc_2023::lines(opt)?.map_while(|line| line.map_err(|e| read_error = Some(e)).ok());
+    let schematic = Schematic::parse_with(lines, &config)?;
+    if let Some(e) = read_error {
+        return Err(e.into());
+    }
+    for warning in schematic.warnings() {
+        report.warn(warning.to_string());
+    }
+    if let Some(path) = &args.svg {
+        std::fs::write(path, Picture::from_schematic(&schematic).to_svg())?;
+    }
+    if let Some(path) = &args.png {
+        let picture = Picture::from_schematic(&schematic);
+        if picture.is_empty() {
+            report.warn(format!(
+                "the schematic is empty, so {} was not written",
+                path.display()
+            ));
+        } else {
+            picture.write_png(BufWriter::new(File::create(path)?))?;
+        }
+    }
+    #[cfg(feature = "serde")]
+    if let Some(path) = &args.export_json {
+        serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), &schematic)?;
+    }
+    if let Some(path) = &args.diff {
+        let text = std::fs::read_to_string(path)?;
+        let other = Schematic::parse_with(text.lines(), &config)?;
+        let diff = schematic.diff(&other);
+        if diff.is_empty() {
+            report.detail(format!("No differences with {}", path.display()));
+        } else {
+            report.detail(format!("Differences with {}:\n{diff}\n", path.display()));
+        }
+    }
 
-    let schematic = Schematic::parse(lines.iter().map(|s| s.as_str()));
     let part_numbers = schematic.get_valid_part_numbers();
     let gears = schematic.get_gears();
 
-    println!("Schematic:");
-    println!("{schematic}\n");
-    println!("Part numbers: {part_numbers:?}");
-    println!("Gears: {:?}", gears);
-    println!("Sum: {}", part_numbers.iter().sum::<u64>());
-    println!(
-        "Gear Ratio Sum: {}",
-        gears.iter().map(|g| g.ratio()).sum::<u64>()
-    );
+    if let Some(style) = args.render {
+        report.detail(format!("{}\n", render(&schematic, style)));
+    } else {
+        report.detail(format!("Schematic:\n{schematic}\n"));
+    }
+    report.detail(format!("Part numbers: {part_numbers:?}"));
+    report.detail(format!("Gears: {:?}", gears));
+    let part_sum = part_numbers
+        .iter()
+        .try_fold(0u64, |sum, value| sum.checked_add(*value))
+        .ok_or_else(|| eyre!("the sum of the part numbers does not fit in 64 bits"))?;
+    let gear_ratio_sum = gears
+        .iter()
+        .try_fold(0u64, |sum, gear| sum.checked_add(gear.value?))
+        .ok_or_else(|| eyre!("the sum of the gear ratios does not fit in 64 bits"))?;
+    report.answer(1, "Sum", part_sum);
+    report.answer(2, "Gear Ratio Sum", gear_ratio_sum);
+    if let Some(expected) = opt.expected_answers()? {
+        report.check(&expected);
+    }
+    report.print(opt.format);
 
     Ok(())
 }
@@ -1,11 +1,13 @@
 use color_eyre::eyre::{eyre, Result};
 use regex::Regex;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     pub game_id: u64,
     pub draws: Vec<Draw>,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Draw {
     pub num_red: u64,
     pub num_blue: u64,
@@ -31,7 +33,7 @@ impl Game {
 
         let draws = draws_str
             .split(";")
-            .map(|s| Draw::parse(s))
+            .map(Draw::parse)
             .collect::<Result<Vec<_>>>()?;
 
         Ok(Game { game_id, draws })
@@ -0,0 +1,141 @@
+use color_eyre::eyre::{eyre, Result};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+// Expected answers for a puzzle, keyed by part number
+pub type Answers = BTreeMap<u32, u64>;
+
+pub const DAYS: std::ops::RangeInclusive<u32> = 1..=25;
+
+// A local directory of puzzle inputs, laid out as:
+//
+//   day03.txt              the real puzzle input
+//   day03.example.txt      the example input from the puzzle description
+//   day03.example.answers  the expected answers for the example, one `partN: value` per line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayInputs {
+    pub day: u32,
+    pub input: bool,
+    pub example: bool,
+    pub answers: bool,
+}
+
+impl InputCache {
+    pub fn new(dir: Option<PathBuf>) -> InputCache {
+        let dir = dir.unwrap_or_else(|| {
+            dirs::cache_dir()
+                .unwrap_or_default()
+                .join(env!("CARGO_PKG_NAME"))
+        });
+        InputCache { dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn input_path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{day:02}.txt"))
+    }
+
+    pub fn example_path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{day:02}.example.txt"))
+    }
+
+    pub fn answers_path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{day:02}.example.answers"))
+    }
+
+    pub fn path(&self, day: u32, example: bool) -> PathBuf {
+        if example {
+            self.example_path(day)
+        } else {
+            self.input_path(day)
+        }
+    }
+
+    pub fn list(&self) -> Vec<DayInputs> {
+        DAYS.map(|day| DayInputs {
+            day,
+            input: self.input_path(day).is_file(),
+            example: self.example_path(day).is_file(),
+            answers: self.answers_path(day).is_file(),
+        })
+        .collect()
+    }
+
+    pub fn add_input(&self, day: u32, source: &Path) -> Result<PathBuf> {
+        self.copy_in(source, self.input_path(day))
+    }
+
+    // Answers left over from a previous example don't apply to the new one, so without answers any
+    // old ones are removed
+    pub fn add_example(&self, day: u32, source: &Path, answers: &Answers) -> Result<PathBuf> {
+        let path = self.copy_in(source, self.example_path(day))?;
+        let answers_path = self.answers_path(day);
+        if answers.is_empty() {
+            if answers_path.is_file() {
+                std::fs::remove_file(answers_path)?;
+            }
+        } else {
+            let mut file = File::create(answers_path)?;
+            for (part, value) in answers {
+                writeln!(file, "part{part}: {value}")?;
+            }
+        }
+        Ok(path)
+    }
+
+    pub fn expected_answers(&self, day: u32) -> Result<Option<Answers>> {
+        let path = self.answers_path(day);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        std::fs::read_to_string(&path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (part, value) = line
+                    .split_once(':')
+                    .ok_or_else(|| eyre!("failed to parse answer \"{line}\" in {path:?}"))?;
+                let part = part
+                    .trim()
+                    .strip_prefix("part")
+                    .ok_or_else(|| eyre!("failed to parse part \"{part}\" in {path:?}"))?
+                    .parse()?;
+                Ok((part, value.trim().parse()?))
+            })
+            .collect::<Result<Answers>>()
+            .map(Some)
+    }
+
+    fn copy_in(&self, source: &Path, dest: PathBuf) -> Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::copy(source, &dest)?;
+        Ok(dest)
+    }
+}
+
+impl std::fmt::Display for DayInputs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mark = |present| if present { "✓" } else { "-" };
+        write!(
+            f,
+            "day {:02}  input {}  example {}  answers {}",
+            self.day,
+            mark(self.input),
+            mark(self.example),
+            mark(self.answers)
+        )
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    geometry::{Bounds, Direction, Vec2},
+    grid::Grid,
+};
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    // Only orthogonally adjacent cells are connected
+    Four,
+    // Diagonally adjacent cells are connected too
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(&self) -> &'static [Direction] {
+        match self {
+            Connectivity::Four => &Direction::CARDINAL,
+            Connectivity::Eight => &Direction::ALL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    // Cells in the order the flood fill reached them, starting from the first one in reading order
+    pub cells: Vec<Vec2>,
+    pub bounds: Bounds<Vec2>,
+}
+
+impl Component {
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Labels {
+    // The index into `components` of the component each cell belongs to, if any
+    pub labels: Grid<Option<usize>>,
+    // Components sorted by their first cell in reading order
+    pub components: Vec<Component>,
+}
+
+impl Labels {
+    pub fn component_at(&self, pos: Vec2) -> Option<&Component> {
+        let label = (*self.labels.get(pos)?)?;
+        self.components.get(label)
+    }
+}
+
+impl<T> Grid<T> {
+    // All the cells connected to `start` for which `pred` holds, or `None` if it doesn't hold for
+    // `start` itself
+    pub fn flood_fill<P: FnMut(&T) -> bool>(
+        &self,
+        start: Vec2,
+        connectivity: Connectivity,
+        mut pred: P,
+    ) -> Option<Component> {
+        let mut labels = self.map(|_| None);
+        self.fill_from(start, 0, connectivity, &mut pred, &mut labels)
+    }
+
+    // Splits the cells for which `pred` holds into connected components
+    pub fn components<P: FnMut(&T) -> bool>(
+        &self,
+        connectivity: Connectivity,
+        mut pred: P,
+    ) -> Labels {
+        let mut labels = self.map(|_| None);
+        let mut components = Vec::new();
+
+        for y in 0..self.height() as i64 {
+            for x in 0..self.width() as i64 {
+                let pos = Vec2::new(x, y);
+                if labels[pos].is_some() {
+                    continue;
+                }
+                let label = components.len();
+                if let Some(component) =
+                    self.fill_from(pos, label, connectivity, &mut pred, &mut labels)
+                {
+                    components.push(component);
+                }
+            }
+        }
+
+        Labels { labels, components }
+    }
+
+    fn fill_from<P: FnMut(&T) -> bool>(
+        &self,
+        start: Vec2,
+        label: usize,
+        connectivity: Connectivity,
+        pred: &mut P,
+        labels: &mut Grid<Option<usize>>,
+    ) -> Option<Component> {
+        if !self.get(start).is_some_and(&mut *pred) {
+            return None;
+        }
+
+        let mut cells = Vec::new();
+        let mut bounds = Bounds::new(start, start);
+        let mut queue = VecDeque::from([start]);
+        labels[start] = Some(label);
+
+        while let Some(pos) = queue.pop_front() {
+            cells.push(pos);
+            bounds.extend(pos);
+
+            for (next, cell) in self.neighbours_in(pos, connectivity.directions()) {
+                if labels[next].is_none() && pred(cell) {
+                    labels[next] = Some(label);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Some(Component { cells, bounds })
+    }
+}
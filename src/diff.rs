@@ -0,0 +1,205 @@
+use crate::{
+    engine::{Gear, Part, PartGraph, Schematic, Vec2},
+    geometry::Bounds,
+    spatial::{Span, SpanIndex},
+};
+use std::collections::HashSet;
+
+// What changed between two schematics. Everything is listed in reading order
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SchematicDiff {
+    pub cells: Vec<CellChange>,
+    // Parts that don't share a cell with any part of the other schematic
+    pub parts_added: Vec<Part>,
+    pub parts_removed: Vec<Part>,
+    // Parts that share cells with a part of the other schematic but whose span or value differs.
+    // Numbers that join up or split appear once for every pair
+    pub parts_altered: Vec<PartChange>,
+    // Parts that are in both schematics, possibly altered, and became valid or invalid
+    pub validity: Vec<ValidityChange>,
+    // Gears are compared as a whole, so a gear whose parts changed is both lost and gained
+    pub gears_gained: Vec<Gear>,
+    pub gears_lost: Vec<Gear>,
+}
+
+// `None` when the cell is outside of that schematic's grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellChange {
+    pub pos: Vec2,
+    pub before: Option<char>,
+    pub after: Option<char>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartChange {
+    pub before: Part,
+    pub after: Part,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidityChange {
+    pub before: Part,
+    pub after: Part,
+    // Whether the part is valid in the other schematic
+    pub valid: bool,
+}
+
+impl Schematic {
+    // How to get from this schematic to `other`
+    pub fn diff(&self, other: &Schematic) -> SchematicDiff {
+        let mut diff = SchematicDiff {
+            cells: cell_changes(self, other),
+            ..Default::default()
+        };
+
+        let (old, new) = (self.graph(), other.graph());
+        let new_parts: SpanIndex<Part> = new
+            .parts()
+            .map(|part| Span {
+                start: part.id.start,
+                len: part.id.len,
+                value: part,
+            })
+            .collect();
+        let mut matched_new = HashSet::new();
+
+        for before in &sorted_parts(old) {
+            let mut matched = false;
+            for Span { value: after, .. } in new_parts.query(cells(before)) {
+                matched = true;
+                matched_new.insert(after.id);
+
+                if before != after {
+                    diff.parts_altered.push(PartChange {
+                        before: *before,
+                        after: *after,
+                    });
+                }
+                let (was_valid, valid) = (is_valid(old, before), is_valid(new, after));
+                if was_valid != valid {
+                    diff.validity.push(ValidityChange {
+                        before: *before,
+                        after: *after,
+                        valid,
+                    });
+                }
+            }
+            if !matched {
+                diff.parts_removed.push(*before);
+            }
+        }
+        diff.parts_added = new_parts
+            .iter()
+            .map(|span| span.value)
+            .filter(|part| !matched_new.contains(&part.id))
+            .collect();
+
+        let old_gears = sorted_gears(self);
+        let new_gears = sorted_gears(other);
+        let (old_set, new_set): (HashSet<_>, HashSet<_>) =
+            (old_gears.iter().collect(), new_gears.iter().collect());
+        diff.gears_lost = old_gears
+            .iter()
+            .filter(|gear| !new_set.contains(gear))
+            .cloned()
+            .collect();
+        diff.gears_gained = new_gears
+            .iter()
+            .filter(|gear| !old_set.contains(gear))
+            .cloned()
+            .collect();
+
+        diff
+    }
+}
+
+impl SchematicDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == SchematicDiff::default()
+    }
+}
+
+fn cell_changes(old: &Schematic, new: &Schematic) -> Vec<CellChange> {
+    let (old, new) = (old.grid(), new.grid());
+    let width = old.width().max(new.width()) as i64;
+    let height = old.height().max(new.height()) as i64;
+
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| Vec2::new(x, y)))
+        .filter_map(|pos| {
+            let (before, after) = (old.get(pos).copied(), new.get(pos).copied());
+            (before != after).then_some(CellChange { pos, before, after })
+        })
+        .collect()
+}
+
+fn cells(part: &Part) -> Bounds<Vec2> {
+    Bounds::new(
+        part.id.start,
+        part.id.start + Vec2::new(part.id.len as i64 - 1, 0),
+    )
+}
+
+fn is_valid(graph: &PartGraph, part: &Part) -> bool {
+    !graph.symbols_adjacent_to(part.id).is_empty()
+}
+
+fn sorted_parts(graph: &PartGraph) -> Vec<Part> {
+    let mut parts: Vec<_> = graph.parts().collect();
+    parts.sort_by_key(|part| (part.id.start.y, part.id.start.x));
+    parts
+}
+
+fn sorted_gears(schematic: &Schematic) -> Vec<Gear> {
+    let mut gears = schematic.get_gears();
+    gears.sort_by_key(|gear| (gear.pos.y, gear.pos.x));
+    gears
+}
+
+fn describe_part(part: &Part) -> String {
+    format!("{} at {}", part.value, part.id.start)
+}
+
+impl std::fmt::Display for SchematicDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = Vec::new();
+        let cell = |c: Option<char>| c.map_or("none".to_string(), |c| format!("'{c}'"));
+
+        for change in &self.cells {
+            lines.push(format!(
+                "cell {}: {} -> {}",
+                change.pos,
+                cell(change.before),
+                cell(change.after)
+            ));
+        }
+        for part in &self.parts_removed {
+            lines.push(format!("- part {}", describe_part(part)));
+        }
+        for part in &self.parts_added {
+            lines.push(format!("+ part {}", describe_part(part)));
+        }
+        for change in &self.parts_altered {
+            lines.push(format!(
+                "~ part {} -> {}",
+                describe_part(&change.before),
+                describe_part(&change.after)
+            ));
+        }
+        for change in &self.validity {
+            lines.push(format!(
+                "part {} is now {}",
+                describe_part(&change.after),
+                if change.valid { "valid" } else { "invalid" }
+            ));
+        }
+        for gear in &self.gears_lost {
+            lines.push(format!("- gear {gear}"));
+        }
+        for gear in &self.gears_gained {
+            lines.push(format!("+ gear {gear}"));
+        }
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
@@ -1,7 +1,17 @@
+pub use crate::geometry::{Direction, Vec2};
+use crate::{
+    components::{Component, Connectivity, Labels},
+    geometry::Bounds,
+    grid::Grid,
+    spatial::{Span, SpanIndex},
+    topology::Topology,
+};
 use lazy_static::lazy_static;
 use std::{
     collections::{HashMap, HashSet},
     iter::Iterator,
+    ops::RangeInclusive,
+    sync::Arc,
 };
 
 lazy_static! {
@@ -15,213 +25,398 @@ lazy_static! {
     };
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ParsedLine {
+    pub digits: Vec<(Vec2, Digit)>,
+    pub symbols: Vec<(Vec2, Symbol)>,
+    pub warnings: Vec<ParseWarning>,
+}
+
+// Serialized as its text and config, along with the parts, symbols and gears found in it.
+// Deserializing parses the text again, so only the text and config are read back
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "SchematicData", try_from = "SchematicData")
+)]
 pub struct Schematic {
-    digits: HashMap<Vec2, Digit>,
+    digits: SpanIndex<Digit>,
     symbols: HashMap<Vec2, Symbol>,
-    grid: Vec<Vec<char>>,
-    longest_digit: u64,
+    grid: Grid<char>,
+    config: SchematicConfig,
+    warnings: Vec<ParseWarning>,
+    graph: PartGraph,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Vec2 {
-    pub x: i64,
-    pub y: i64,
+// Decides which characters of a schematic count as symbols
+#[derive(Clone)]
+pub enum SymbolClass {
+    Set(HashSet<char>),
+    // Anything that isn't a digit or the background
+    AnyExceptBackground,
+    Custom(Arc<dyn Fn(char) -> bool + Send + Sync>),
 }
 
-impl Vec2 {
-    pub fn new(x: i64, y: i64) -> Vec2 {
-        Vec2 { x, y }
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchematicConfig {
+    pub symbols: SymbolClass,
+    pub background: char,
+    // Decides which cells are adjacent at the edges. Numbers never continue across an edge
+    pub topology: Topology,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    #[error("number starting at {pos} does not fit in 64 bits")]
+    Overflow { pos: Vec2 },
+    #[error("{pos} is outside of the schematic")]
+    OutOfBounds { pos: Vec2 },
+}
+
+// A character that is neither a digit, a symbol nor the background. It is otherwise ignored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseWarning {
+    pub pos: Vec2,
+    pub c: char,
+}
+
+// Identifies a part number by where it is written: the position of its first digit and how many
+// cells it spans. Unlike the value, this is unique within a schematic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartId {
+    pub start: Vec2,
+    pub len: u64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Part {
+    pub id: PartId,
+    pub value: u64,
+}
+
+// Which parts touch which symbols. Every part and symbol of the schematic is a node, even if it
+// has no edges
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PartGraph {
+    parts: HashMap<PartId, u64>,
+    symbols: HashMap<Vec2, Symbol>,
+    part_symbols: HashMap<PartId, Vec<Vec2>>,
+    symbol_parts: HashMap<Vec2, Vec<PartId>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gear {
     pub pos: Vec2,
-    pub parts: (u64, u64),
+    pub symbol: Symbol,
+    pub parts: Vec<Part>,
+    // The parts combined with the aggregation of the rule that found this gear, or `None` if that
+    // doesn't fit in 64 bits
+    pub value: Option<u64>,
 }
 
-impl Gear {
-    pub fn ratio(&self) -> u64 {
-        self.parts.0 * self.parts.1
-    }
+// Describes which symbols count as gears: those in `symbols` (or any symbol if `None`) that touch
+// a number of parts within `parts`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    pub symbols: Option<HashSet<char>>,
+    pub parts: RangeInclusive<usize>,
+    pub aggregation: Aggregation,
+}
+
+// Returns `None` if the result doesn't fit in 64 bits, like the built-in aggregations
+pub type AggregationFn = Arc<dyn Fn(&[Part]) -> Option<u64> + Send + Sync>;
+
+#[derive(Clone)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Custom(AggregationFn),
 }
 
 impl Schematic {
-    pub fn parse<'a, I: Iterator<Item = &'a str>>(lines: I) -> Schematic {
-        let mut digits = HashMap::new();
+    pub fn parse<S: AsRef<str>, I: Iterator<Item = S>>(lines: I) -> Result<Schematic, ParseError> {
+        Self::parse_with(lines, &SchematicConfig::default())
+    }
+
+    // Takes borrowed or owned lines, so they can come straight from a reader
+    pub fn parse_with<S: AsRef<str>, I: Iterator<Item = S>>(
+        lines: I,
+        config: &SchematicConfig,
+    ) -> Result<Schematic, ParseError> {
+        let lines: Vec<_> = lines.collect();
+        let grid = Grid::parse(lines.iter().map(|line| line.as_ref()), config.background);
+
+        let mut digits = SpanIndex::new();
         let mut symbols = HashMap::new();
-        let mut grid = Vec::new();
+        let mut warnings = Vec::new();
 
-        for (y, line) in lines.enumerate() {
-            grid.push(line.chars().collect::<Vec<_>>());
-            let (new_digits, new_symbols) = Self::parse_line(line, y as u64);
-            digits.extend(new_digits.into_iter());
-            symbols.extend(new_symbols.into_iter());
+        for (y, line) in lines.iter().enumerate() {
+            let parsed = Self::parse_line_with(line.as_ref(), y as u64, config)?;
+            for (pos, digit) in parsed.digits {
+                digits.insert(pos, digit.len(), digit);
+            }
+            symbols.extend(parsed.symbols);
+            warnings.extend(parsed.warnings);
         }
 
-        let longest_digit = digits.iter().map(|(_, d)| d.len()).max().unwrap_or(0) as u64;
-
-        Schematic {
+        let mut schematic = Schematic {
             digits,
             symbols,
             grid,
-            longest_digit,
-        }
+            config: config.clone(),
+            warnings,
+            graph: PartGraph::default(),
+        };
+        schematic.graph = schematic.build_graph();
+        Ok(schematic)
+    }
+
+    pub fn parse_line(line: &str, y: u64) -> Result<ParsedLine, ParseError> {
+        Self::parse_line_with(line, y, &SchematicConfig::default())
     }
 
-    pub fn parse_line(line: &str, y: u64) -> (Vec<(Vec2, Digit)>, Vec<(Vec2, Symbol)>) {
+    pub fn parse_line_with(
+        line: &str,
+        y: u64,
+        config: &SchematicConfig,
+    ) -> Result<ParsedLine, ParseError> {
         let y = y as i64;
 
         let mut digits = Vec::new();
         let mut symbols = Vec::new();
+        let mut warnings = Vec::new();
 
-        let mut curr_digit = None;
+        // The start of the number being read, and its value so far. The span comes from where the
+        // digits are in the line, so leading zeroes are part of it
+        let mut curr_digit: Option<(i64, u64)> = None;
+        let mut line_len = 0;
 
         for (x, c) in line.chars().enumerate() {
             let x = x as i64;
+            line_len = x + 1;
             if let Some(d) = c.to_digit(10) {
-                if let Some(curr) = &mut curr_digit {
-                    *curr = *curr * 10 + d;
-                } else {
-                    curr_digit = Some(d);
-                }
+                let (start, value) = curr_digit.unwrap_or((x, 0));
+                let value = value
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(d as u64))
+                    .ok_or(ParseError::Overflow {
+                        pos: Vec2::new(start, y),
+                    })?;
+                curr_digit = Some((start, value));
             } else {
-                if let Some(curr) = curr_digit {
-                    let digit = Digit(curr as u64);
-                    digits.push((Vec2::new(x - digit.len() as i64, y), digit));
-                    curr_digit = None;
+                if let Some((start, value)) = curr_digit.take() {
+                    digits.push((Vec2::new(start, y), Digit::new(value, x - start)));
                 }
 
-                if SYMBOLS.contains(&c) {
+                if c == config.background {
+                    continue;
+                } else if config.is_symbol(c) {
                     symbols.push((Vec2::new(x, y), Symbol(c)));
+                } else {
+                    warnings.push(ParseWarning {
+                        pos: Vec2::new(x, y),
+                        c,
+                    });
                 }
             }
         }
 
         // In case a digit is at the end of the line
-        if let Some(curr) = curr_digit {
-            let digit = Digit(curr as u64);
-            let x_idx = line.len() as i64 - digit.len() as i64;
-            digits.push((Vec2::new(x_idx, y), digit));
+        if let Some((start, value)) = curr_digit {
+            digits.push((Vec2::new(start, y), Digit::new(value, line_len - start)));
         }
 
-        (digits, symbols)
+        Ok(ParsedLine {
+            digits,
+            symbols,
+            warnings,
+        })
     }
 
-    pub fn get_valid_part_numbers(&self) -> Vec<u64> {
-        self.digits
-            .iter()
-            .filter_map(|(pos, digit)| {
-                if self.has_symbol_around(*pos, digit) {
-                    Some(digit.0)
-                } else {
-                    None
-                }
-            })
-            .collect()
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
     }
 
-    pub fn get_gears(&self) -> Vec<Gear> {
-        self.symbols
-            .iter()
-            .filter_map(|(pos, symbol)| {
-                let pos = *pos;
-                if symbol.0 == '*' {
-                    self.get_gear_parts(pos).map(|parts| Gear { pos, parts })
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
+    }
+
+    pub fn config(&self) -> &SchematicConfig {
+        &self.config
+    }
+
+    // Changes one cell, updating only the numbers, symbols and graph edges around it. The result
+    // is the same as parsing the edited text again. On error the schematic is left unchanged
+    pub fn set(&mut self, pos: Vec2, c: char) -> Result<(), ParseError> {
+        let old = *self.grid.get(pos).ok_or(ParseError::OutOfBounds { pos })?;
+        if old == c {
+            return Ok(());
+        }
+
+        // The number under `pos` and those right next to it can grow, shrink, split or join up.
+        // Reading the row again from the first of them to the last covers every number that can
+        // change, as the cells just outside aren't digits
+        let around = Bounds::new(pos - Vec2::new(1, 0), pos + Vec2::new(1, 0));
+        let old_spans: Vec<_> = self.digits.query(around).copied().collect();
+        let start = old_spans
+            .first()
+            .map_or(pos.x, |span| span.start.x.min(pos.x));
+        let end = old_spans
+            .last()
+            .map_or(pos.x, |span| (span.end() - 1).max(pos.x));
+        let segment: String = (start..=end)
+            .map(|x| {
+                if x == pos.x {
+                    c
                 } else {
-                    None
+                    self.grid[Vec2::new(x, pos.y)]
                 }
             })
-            .collect()
+            .collect();
+        let offset = Vec2::new(start, 0);
+        let parsed = Self::parse_line_with(&segment, pos.y as u64, &self.config).map_err(
+            |err| match err {
+                ParseError::Overflow { pos } => ParseError::Overflow { pos: pos + offset },
+                err => err,
+            },
+        )?;
+
+        self.grid[pos] = c;
+        self.warnings.retain(|warning| warning.pos != pos);
+        for span in old_spans {
+            self.digits.remove(span.start);
+            self.graph.remove_part(PartId::new(span.start, &span.value));
+        }
+        if self.symbols.remove(&pos).is_some() {
+            self.graph.remove_symbol(pos);
+        }
+
+        if c.is_ascii_digit() || c == self.config.background {
+            // Digits were handled with the rest of the number
+        } else if self.config.is_symbol(c) {
+            self.symbols.insert(pos, Symbol(c));
+            self.graph.symbols.insert(pos, Symbol(c));
+            for part in self.get_gear_parts(pos) {
+                self.graph.add_edge(part.id, pos);
+            }
+        } else {
+            let idx = self
+                .warnings
+                .partition_point(|warning| (warning.pos.y, warning.pos.x) < (pos.y, pos.x));
+            self.warnings.insert(idx, ParseWarning { pos, c });
+        }
+
+        for (start, digit) in parsed.digits {
+            let id = PartId::new(start + offset, &digit);
+            self.digits.insert(id.start, digit.len(), digit);
+            self.graph.parts.insert(id, digit.value);
+            let symbols: Vec<_> = id
+                .cells()
+                .flat_map(|cell| self.cells_around(cell))
+                .filter(|pos| self.symbols.contains_key(pos))
+                .collect();
+            for symbol in symbols {
+                self.graph.add_edge(id, symbol);
+            }
+        }
+
+        Ok(())
     }
 
-    fn has_symbol_around(&self, pos: Vec2, digit: &Digit) -> bool {
-        // We need to check:
-        // - Blocks to the left and right
-        // - Blocks on 4 corners
-        // - The 1 or more blocks above and below
-        // Each of these are in their own category
+    // Sets the cell to the background
+    pub fn clear(&mut self, pos: Vec2) -> Result<(), ParseError> {
+        self.set(pos, self.config.background)
+    }
 
-        let digit_len = digit.len() as i64;
+    pub fn components<P: FnMut(&char) -> bool>(
+        &self,
+        connectivity: Connectivity,
+        pred: P,
+    ) -> Labels {
+        self.grid.components(connectivity, pred)
+    }
 
-        // left and right
-        let left = self.has_symbol(pos + Vec2::new(-1, 0));
-        let right = self.has_symbol(pos + Vec2::new(digit_len, 0));
+    // Clusters of digits and symbols that touch each other, diagonals included
+    pub fn machine_parts(&self) -> Vec<Component> {
+        self.components(Connectivity::Eight, |c| *c != self.config.background)
+            .components
+    }
 
-        // Four corners
-        let top_left = self.has_symbol(pos + Vec2::new(-1, -1));
-        let top_right = self.has_symbol(pos + Vec2::new(digit_len, -1));
-        let bottom_left = self.has_symbol(pos + Vec2::new(-1, 1));
-        let bottom_right = self.has_symbol(pos + Vec2::new(digit_len, 1));
+    pub fn graph(&self) -> &PartGraph {
+        &self.graph
+    }
 
-        // Top and bottom
-        let top = (0..digit_len).any(|x| self.has_symbol(pos + Vec2::new(x, -1)));
-        let bottom = (0..digit_len).any(|x| self.has_symbol(pos + Vec2::new(x, 1)));
+    pub fn get_valid_part_numbers(&self) -> Vec<u64> {
+        self.graph.valid_parts().map(|part| part.value).collect()
+    }
 
-        left || right || top_left || top_right || bottom_left || bottom_right || top || bottom
+    // `*` symbols touching exactly two parts, as in the original puzzle
+    pub fn get_gears(&self) -> Vec<Gear> {
+        self.find_gears(&GearRule::default())
     }
 
-    fn has_symbol(&self, pos: Vec2) -> bool {
-        if pos.x < 0
-            || pos.y < 0
-            || pos.y >= self.grid.len() as i64
-            || pos.x >= self.grid[0].len() as i64
-        {
-            return false;
-        }
+    pub fn find_gears(&self, rule: &GearRule) -> Vec<Gear> {
+        self.graph.gears(rule).collect()
+    }
 
-        SYMBOLS.contains(&self.grid[pos.y as usize][pos.x as usize])
+    fn build_graph(&self) -> PartGraph {
+        let mut graph = PartGraph::default();
+        for span in self.digits.iter() {
+            graph
+                .parts
+                .insert(PartId::new(span.start, &span.value), span.value.value);
+        }
+        for (pos, symbol) in &self.symbols {
+            graph.symbols.insert(*pos, *symbol);
+            for part in self.get_gear_parts(*pos) {
+                graph.add_edge(part.id, *pos);
+            }
+        }
+        graph
     }
 
-    fn get_gear_parts(&self, pos: Vec2) -> Option<(u64, u64)> {
-        // The row above and below, we need to check starting from the left corner - the longest
-        // digit, and confirm if any are within the range of this gear
-        let mut part_values = (None, None);
+    fn get_gear_parts(&self, pos: Vec2) -> Vec<Part> {
+        let to_part = |span: &Span<Digit>| Part {
+            id: PartId::new(span.start, &span.value),
+            value: span.value.value,
+        };
 
-        for y in -1..=1 {
-            let mut x = -(self.longest_digit as i64);
-            while x <= 1 {
-                if x == 0 && y == 0 {
-                    x += 1;
-                    continue;
-                }
+        if self.config.topology == Topology::Bounded {
+            // Any number with a cell in the 3x3 box around the symbol touches it
+            let around = Bounds::new(pos - Vec2::new(1, 1), pos + Vec2::new(1, 1));
+            return self.digits.query(around).map(to_part).collect();
+        }
 
-                let curr = Vec2::new(pos.x + x, pos.y + y);
-                if let Some(digit) = self.digits.get(&curr) {
-                    // If this is true, it MUST be touching the gear
-                    if curr.x + digit.len() as i64 >= pos.x {
-                        // Skip over the digit
-                        x += digit.len() as i64;
-
-                        // Add it to the part values
-                        if let (None, None) = part_values {
-                            part_values.0 = Some(digit.0);
-                        } else if let (Some(_), None) = part_values {
-                            part_values.1 = Some(digit.0);
-                        } else {
-                            return None;
-                        }
-                    } else {
-                        x += 1;
-                    }
-                } else {
-                    x += 1;
+        // Past the edges, the cells around the symbol can be anywhere on the grid
+        let mut parts = Vec::new();
+        for cell in self.cells_around(pos) {
+            if let Some(part) = self.digits.get(cell).map(to_part) {
+                if !parts.contains(&part) {
+                    parts.push(part);
                 }
             }
         }
+        parts
+    }
 
-        if let (Some(a), Some(b)) = part_values {
-            Some((a, b))
-        } else {
-            None
-        }
+    fn cells_around(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        self.grid
+            .neighbours_on(pos, &Direction::ALL, self.config.topology)
+            .map(|(pos, _)| pos)
     }
 }
 
 impl std::fmt::Display for Schematic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let digits = self.digits.iter().map(|(v, digit)| format!("{v}: {digit}"));
+        let digits = self
+            .digits
+            .iter()
+            .map(|Span { start, value, .. }| format!("{start}: {value}"));
         let symbols: Vec<_> = self
             .symbols
             .iter()
@@ -244,20 +439,349 @@ impl std::fmt::Display for Schematic {
     }
 }
 
+impl SchematicConfig {
+    pub fn with_symbols<I: IntoIterator<Item = char>>(symbols: I) -> SchematicConfig {
+        SchematicConfig {
+            symbols: SymbolClass::Set(symbols.into_iter().collect()),
+            ..Default::default()
+        }
+    }
+
+    pub fn any_symbol(background: char) -> SchematicConfig {
+        SchematicConfig {
+            symbols: SymbolClass::AnyExceptBackground,
+            background,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_classifier<F: Fn(char) -> bool + Send + Sync + 'static>(f: F) -> SchematicConfig {
+        SchematicConfig {
+            symbols: SymbolClass::Custom(Arc::new(f)),
+            ..Default::default()
+        }
+    }
+
+    pub fn topology(mut self, topology: Topology) -> SchematicConfig {
+        self.topology = topology;
+        self
+    }
+
+    // Only meaningful for characters that are neither digits nor the background
+    pub fn is_symbol(&self, c: char) -> bool {
+        match &self.symbols {
+            SymbolClass::Set(set) => set.contains(&c),
+            SymbolClass::AnyExceptBackground => c != self.background && !c.is_ascii_digit(),
+            SymbolClass::Custom(f) => f(c),
+        }
+    }
+}
+
+impl Default for SchematicConfig {
+    // The ASCII punctuation used by the original puzzle, on a `.` background
+    fn default() -> Self {
+        SchematicConfig {
+            symbols: SymbolClass::Set(SYMBOLS.clone()),
+            background: '.',
+            topology: Topology::Bounded,
+        }
+    }
+}
+
+impl std::fmt::Debug for SymbolClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymbolClass::Set(set) => f.debug_tuple("Set").field(set).finish(),
+            SymbolClass::AnyExceptBackground => write!(f, "AnyExceptBackground"),
+            SymbolClass::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+impl PartialEq for SymbolClass {
+    // Custom classifiers are only equal if they are the same closure
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SymbolClass::Set(a), SymbolClass::Set(b)) => a == b,
+            (SymbolClass::AnyExceptBackground, SymbolClass::AnyExceptBackground) => true,
+            (SymbolClass::Custom(a), SymbolClass::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SymbolClass {}
+
+impl PartId {
+    fn new(start: Vec2, digit: &Digit) -> PartId {
+        PartId {
+            start,
+            len: digit.len(),
+        }
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = Vec2> {
+        let start = self.start;
+        (0..self.len as i64).map(move |x| start + Vec2::new(x, 0))
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        pos.y == self.start.y && pos.x >= self.start.x && pos.x < self.start.x + self.len as i64
+    }
+}
+
+impl PartGraph {
+    pub fn part(&self, id: PartId) -> Option<Part> {
+        self.parts.get(&id).map(|value| Part { id, value: *value })
+    }
+
+    pub fn parts(&self) -> impl Iterator<Item = Part> + '_ {
+        self.parts.iter().map(|(id, value)| Part {
+            id: *id,
+            value: *value,
+        })
+    }
+
+    pub fn symbol(&self, pos: Vec2) -> Option<Symbol> {
+        self.symbols.get(&pos).copied()
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = (Vec2, Symbol)> + '_ {
+        self.symbols.iter().map(|(pos, symbol)| (*pos, *symbol))
+    }
+
+    // Symbol positions touching the part, in reading order
+    pub fn symbols_adjacent_to(&self, part: PartId) -> &[Vec2] {
+        self.part_symbols.get(&part).map_or(&[], |v| v.as_slice())
+    }
+
+    // Parts touching the symbol at `symbol`, in reading order
+    pub fn parts_adjacent_to(&self, symbol: Vec2) -> &[PartId] {
+        self.symbol_parts.get(&symbol).map_or(&[], |v| v.as_slice())
+    }
+
+    // Parts touching at least one symbol
+    pub fn valid_parts(&self) -> impl Iterator<Item = Part> + '_ {
+        self.parts()
+            .filter(|part| !self.symbols_adjacent_to(part.id).is_empty())
+    }
+
+    // Parts not touching any symbol
+    pub fn orphan_parts(&self) -> impl Iterator<Item = Part> + '_ {
+        self.parts()
+            .filter(|part| self.symbols_adjacent_to(part.id).is_empty())
+    }
+
+    pub fn gears<'a>(&'a self, rule: &'a GearRule) -> impl Iterator<Item = Gear> + 'a {
+        self.symbols()
+            .filter(|(_, symbol)| rule.matches_symbol(symbol.0))
+            .filter_map(|(pos, symbol)| {
+                let parts: Vec<_> = self
+                    .parts_adjacent_to(pos)
+                    .iter()
+                    .filter_map(|id| self.part(*id))
+                    .collect();
+                if rule.parts.contains(&parts.len()) {
+                    Some(Gear {
+                        pos,
+                        symbol,
+                        value: rule.aggregation.apply(&parts),
+                        parts,
+                    })
+                } else {
+                    None
+                }
+            })
+    }
+
+    // Edges are kept in reading order, and adding one twice does nothing
+    fn add_edge(&mut self, part: PartId, symbol: Vec2) {
+        let symbols = self.part_symbols.entry(part).or_default();
+        let idx = symbols.partition_point(|pos| (pos.y, pos.x) < (symbol.y, symbol.x));
+        if symbols.get(idx) != Some(&symbol) {
+            symbols.insert(idx, symbol);
+        }
+
+        let parts = self.symbol_parts.entry(symbol).or_default();
+        let idx =
+            parts.partition_point(|id| (id.start.y, id.start.x) < (part.start.y, part.start.x));
+        if parts.get(idx) != Some(&part) {
+            parts.insert(idx, part);
+        }
+    }
+
+    fn remove_part(&mut self, part: PartId) {
+        self.parts.remove(&part);
+        for symbol in self.part_symbols.remove(&part).unwrap_or_default() {
+            remove_edge(&mut self.symbol_parts, symbol, &part);
+        }
+    }
+
+    fn remove_symbol(&mut self, symbol: Vec2) {
+        self.symbols.remove(&symbol);
+        for part in self.symbol_parts.remove(&symbol).unwrap_or_default() {
+            remove_edge(&mut self.part_symbols, part, &symbol);
+        }
+    }
+}
+
+// Removes `to` from the edges of `from`, forgetting `from` if it has none left, as a freshly built
+// graph would
+fn remove_edge<K: Eq + std::hash::Hash, V: PartialEq>(
+    edges: &mut HashMap<K, Vec<V>>,
+    from: K,
+    to: &V,
+) {
+    if let Some(list) = edges.get_mut(&from) {
+        list.retain(|v| v != to);
+        if list.is_empty() {
+            edges.remove(&from);
+        }
+    }
+}
+
+impl GearRule {
+    pub fn symbols<I: IntoIterator<Item = char>>(mut self, symbols: I) -> GearRule {
+        self.symbols = Some(symbols.into_iter().collect());
+        self
+    }
+
+    pub fn any_symbol(mut self) -> GearRule {
+        self.symbols = None;
+        self
+    }
+
+    pub fn exactly(self, parts: usize) -> GearRule {
+        self.parts(parts..=parts)
+    }
+
+    pub fn parts(mut self, parts: RangeInclusive<usize>) -> GearRule {
+        self.parts = parts;
+        self
+    }
+
+    pub fn aggregation(mut self, aggregation: Aggregation) -> GearRule {
+        self.aggregation = aggregation;
+        self
+    }
+
+    pub fn matches_symbol(&self, c: char) -> bool {
+        self.symbols
+            .as_ref()
+            .is_none_or(|symbols| symbols.contains(&c))
+    }
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        GearRule {
+            symbols: Some(HashSet::from(['*'])),
+            parts: 2..=2,
+            aggregation: Aggregation::Product,
+        }
+    }
+}
+
+impl Aggregation {
+    pub fn custom<F: Fn(&[Part]) -> Option<u64> + Send + Sync + 'static>(f: F) -> Aggregation {
+        Aggregation::Custom(Arc::new(f))
+    }
+
+    // `None` if the result overflows. Part numbers can be as large as `u64::MAX`, so even two of
+    // them can be too much
+    pub fn apply(&self, parts: &[Part]) -> Option<u64> {
+        let mut values = parts.iter().map(|part| part.value);
+        match self {
+            Aggregation::Product => values.try_fold(1u64, |acc, value| acc.checked_mul(value)),
+            Aggregation::Sum => values.try_fold(0u64, |acc, value| acc.checked_add(value)),
+            Aggregation::Custom(f) => f(parts),
+        }
+    }
+}
+
+impl std::fmt::Debug for Aggregation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Aggregation::Product => write!(f, "Product"),
+            Aggregation::Sum => write!(f, "Sum"),
+            Aggregation::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+impl PartialEq for Aggregation {
+    // Custom aggregations are only equal if they are the same closure
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Aggregation::Product, Aggregation::Product) => true,
+            (Aggregation::Sum, Aggregation::Sum) => true,
+            (Aggregation::Custom(a), Aggregation::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Aggregation {}
+
+// The gear doesn't know which aggregation combined its parts, so they are listed rather than
+// joined with an operator that could be the wrong one
+impl std::fmt::Display for Gear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<_> = self
+            .parts
+            .iter()
+            .map(|part| part.value.to_string())
+            .collect();
+        write!(
+            f,
+            "{} at {}: parts {} = ",
+            self.symbol,
+            self.pos,
+            parts.join(", ")
+        )?;
+        match self.value {
+            Some(value) => write!(f, "{value}"),
+            None => write!(f, "overflow"),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unclassified character '{}' at {}", self.c, self.pos)
+    }
+}
+
 trait Block {
     fn len(&self) -> u64;
 }
 
+// A number written in the schematic. `len` is the number of characters it takes up, which can be
+// more than its value needs if it has leading zeroes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Digit(pub u64);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Digit {
+    pub value: u64,
+    pub len: u64,
+}
+
+impl Digit {
+    fn new(value: u64, len: i64) -> Digit {
+        Digit {
+            value,
+            len: len as u64,
+        }
+    }
+}
 
 impl std::fmt::Display for Digit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{:0width$}", self.value, width = self.len as usize)
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Symbol(pub char);
 
 impl std::fmt::Display for Symbol {
@@ -268,7 +792,7 @@ impl std::fmt::Display for Symbol {
 
 impl Block for Digit {
     fn len(&self) -> u64 {
-        self.0.ilog10() as u64 + 1
+        self.len
     }
 }
 
@@ -278,36 +802,116 @@ impl Block for Symbol {
     }
 }
 
-impl std::fmt::Display for Vec2 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{}, {}]", self.x, self.y)
-    }
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SchematicData {
+    rows: Vec<String>,
+    background: char,
+    symbol_class: SymbolClassData,
+    #[serde(default)]
+    topology: Topology,
+    #[serde(default, skip_deserializing)]
+    parts: Vec<PartData>,
+    #[serde(default, skip_deserializing)]
+    symbols: Vec<SymbolData>,
+    #[serde(default, skip_deserializing)]
+    gears: Vec<Gear>,
+    #[serde(default, skip_deserializing)]
+    warnings: Vec<ParseWarning>,
 }
 
-impl std::ops::Add for Vec2 {
-    type Output = Vec2;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Vec2::new(self.x + rhs.x, self.y + rhs.y)
-    }
+// Custom classifiers can't be written out, so they are replaced by the set of symbols they found
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SymbolClassData {
+    Set(Vec<char>),
+    AnyExceptBackground,
 }
 
-impl std::ops::AddAssign for Vec2 {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = *self + rhs;
-    }
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct PartData {
+    #[serde(flatten)]
+    part: Part,
+    valid: bool,
 }
 
-impl std::ops::Sub for Vec2 {
-    type Output = Vec2;
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct SymbolData {
+    pos: Vec2,
+    symbol: Symbol,
+}
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+#[cfg(feature = "serde")]
+impl From<Schematic> for SchematicData {
+    fn from(schematic: Schematic) -> Self {
+        let reading_order = |pos: &Vec2| (pos.y, pos.x);
+
+        let symbol_class = match &schematic.config.symbols {
+            SymbolClass::Set(set) => SymbolClassData::Set(sorted_chars(set.iter().copied())),
+            SymbolClass::AnyExceptBackground => SymbolClassData::AnyExceptBackground,
+            SymbolClass::Custom(_) => SymbolClassData::Set(sorted_chars(
+                schematic.symbols.values().map(|symbol| symbol.0),
+            )),
+        };
+
+        let graph = &schematic.graph;
+        let mut parts: Vec<_> = graph
+            .parts()
+            .map(|part| PartData {
+                part,
+                valid: !graph.symbols_adjacent_to(part.id).is_empty(),
+            })
+            .collect();
+        parts.sort_by_key(|data| reading_order(&data.part.id.start));
+        let mut symbols: Vec<_> = graph
+            .symbols()
+            .map(|(pos, symbol)| SymbolData { pos, symbol })
+            .collect();
+        symbols.sort_by_key(|data| reading_order(&data.pos));
+        let mut gears = schematic.get_gears();
+        gears.sort_by_key(|gear| reading_order(&gear.pos));
+
+        SchematicData {
+            rows: schematic
+                .grid
+                .rows()
+                .map(|row| row.iter().collect())
+                .collect(),
+            background: schematic.config.background,
+            symbol_class,
+            topology: schematic.config.topology,
+            parts,
+            symbols,
+            gears,
+            warnings: schematic.warnings,
+        }
     }
 }
 
-impl std::ops::SubAssign for Vec2 {
-    fn sub_assign(&mut self, rhs: Self) {
-        *self = *self - rhs;
+#[cfg(feature = "serde")]
+fn sorted_chars<I: Iterator<Item = char>>(chars: I) -> Vec<char> {
+    chars
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SchematicData> for Schematic {
+    type Error = ParseError;
+
+    fn try_from(data: SchematicData) -> Result<Self, Self::Error> {
+        let config = SchematicConfig {
+            symbols: match data.symbol_class {
+                SymbolClassData::Set(set) => SymbolClass::Set(set.into_iter().collect()),
+                SymbolClassData::AnyExceptBackground => SymbolClass::AnyExceptBackground,
+            },
+            background: data.background,
+            topology: data.topology,
+        };
+        Schematic::parse_with(data.rows.iter().map(|row| row.as_str()), &config)
     }
 }
@@ -0,0 +1,367 @@
+use crate::{
+    components::Component,
+    engine::Schematic,
+    geometry::{Bounds, Vec2},
+    grid::Grid,
+};
+use std::{collections::HashSet, fmt::Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellClass {
+    Empty,
+    // A digit that is not part of a valid part number
+    Digit,
+    Symbol,
+    // A digit of a valid part number
+    Part,
+    Gear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Colour(pub u8, pub u8, pub u8);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Overlay {
+    // A line through the centres of the cells, in order
+    Path {
+        cells: Vec<Vec2>,
+        colour: Colour,
+    },
+    // An outline around a group of cells
+    Area {
+        bounds: Bounds<Vec2>,
+        colour: Colour,
+    },
+}
+
+// A picture of a grid, with one square per cell coloured by its class, that can be exported as SVG
+// or PNG. Glyphs are only drawn in SVG, as PNG output doesn't rasterize text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    classes: Grid<CellClass>,
+    glyphs: Option<Grid<char>>,
+    overlays: Vec<Overlay>,
+    cell_size: u32,
+}
+
+impl CellClass {
+    pub fn colour(&self) -> Colour {
+        match self {
+            CellClass::Empty => Colour(0xff, 0xff, 0xff),
+            CellClass::Digit => Colour(0xe0, 0x6c, 0x75),
+            CellClass::Symbol => Colour(0x61, 0xaf, 0xef),
+            CellClass::Part => Colour(0x98, 0xc3, 0x79),
+            CellClass::Gear => Colour(0xe5, 0xc0, 0x7b),
+        }
+    }
+}
+
+impl Colour {
+    pub const BLACK: Colour = Colour(0, 0, 0);
+    pub const GRID: Colour = Colour(0xdd, 0xdd, 0xdd);
+    pub const PATH: Colour = Colour(0xc6, 0x78, 0xdd);
+    pub const AREA: Colour = Colour(0x56, 0xb6, 0xc2);
+}
+
+impl Picture {
+    pub fn new(classes: Grid<CellClass>) -> Picture {
+        Picture {
+            classes,
+            glyphs: None,
+            overlays: Vec::new(),
+            cell_size: 16,
+        }
+    }
+
+    pub fn from_grid<T, F: FnMut(&T) -> CellClass>(grid: &Grid<T>, classify: F) -> Picture {
+        Picture::new(grid.map(classify))
+    }
+
+    pub fn from_schematic(schematic: &Schematic) -> Picture {
+        let graph = schematic.graph();
+        let valid_cells: HashSet<_> = graph
+            .valid_parts()
+            .flat_map(|part| part.id.cells())
+            .collect();
+        let gears: HashSet<_> = schematic.get_gears().iter().map(|gear| gear.pos).collect();
+
+        let grid = schematic.grid();
+        let mut classes = grid.map(|_| CellClass::Empty);
+        for (pos, c) in grid.iter() {
+            classes[pos] = if gears.contains(&pos) {
+                CellClass::Gear
+            } else if graph.symbol(pos).is_some() {
+                CellClass::Symbol
+            } else if valid_cells.contains(&pos) {
+                CellClass::Part
+            } else if c.is_ascii_digit() {
+                CellClass::Digit
+            } else {
+                CellClass::Empty
+            };
+        }
+
+        Picture::new(classes).glyphs(grid.clone())
+    }
+
+    pub fn glyphs(mut self, glyphs: Grid<char>) -> Picture {
+        self.glyphs = Some(glyphs);
+        self
+    }
+
+    pub fn cell_size(mut self, cell_size: u32) -> Picture {
+        self.cell_size = cell_size.max(1);
+        self
+    }
+
+    pub fn overlay_path(mut self, cells: &[Vec2], colour: Colour) -> Picture {
+        self.overlays.push(Overlay::Path {
+            cells: cells.to_vec(),
+            colour,
+        });
+        self
+    }
+
+    pub fn overlay_component(mut self, component: &Component, colour: Colour) -> Picture {
+        self.overlays.push(Overlay::Area {
+            bounds: component.bounds,
+            colour,
+        });
+        self
+    }
+
+    pub fn width(&self) -> u32 {
+        self.classes.width() as u32 * self.cell_size
+    }
+
+    pub fn height(&self) -> u32 {
+        self.classes.height() as u32 * self.cell_size
+    }
+
+    // Empty pictures can still be written as SVG, but PNG requires at least one pixel
+    pub fn is_empty(&self) -> bool {
+        self.width() == 0 || self.height() == 0
+    }
+
+    pub fn to_svg(&self) -> String {
+        let size = self.cell_size;
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = self.width(),
+            h = self.height()
+        )
+        .unwrap();
+
+        for (pos, class) in self.classes.iter() {
+            writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{size}" height="{size}" fill="{}" stroke="{}"/>"#,
+                pos.x * size as i64,
+                pos.y * size as i64,
+                class.colour(),
+                Colour::GRID
+            )
+            .unwrap();
+        }
+
+        if let Some(glyphs) = &self.glyphs {
+            for (pos, c) in glyphs.iter() {
+                if !self
+                    .classes
+                    .get(pos)
+                    .is_some_and(|class| *class != CellClass::Empty)
+                {
+                    continue;
+                }
+                let (cx, cy) = self.centre(pos);
+                writeln!(
+                    svg,
+                    r#"<text x="{cx}" y="{cy}" font-family="monospace" font-size="{}" text-anchor="middle" dominant-baseline="central" fill="{}">{}</text>"#,
+                    size * 3 / 4,
+                    Colour::BLACK,
+                    escape(*c)
+                )
+                .unwrap();
+            }
+        }
+
+        for overlay in &self.overlays {
+            match overlay {
+                Overlay::Path { cells, colour } => {
+                    let points: Vec<_> = cells
+                        .iter()
+                        .map(|pos| {
+                            let (x, y) = self.centre(*pos);
+                            format!("{x},{y}")
+                        })
+                        .collect();
+                    writeln!(
+                        svg,
+                        r#"<polyline points="{}" fill="none" stroke="{colour}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+                        points.join(" "),
+                        size / 4 + 1
+                    )
+                    .unwrap();
+                }
+                Overlay::Area { bounds, colour } => {
+                    writeln!(
+                        svg,
+                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{colour}" stroke-width="2"/>"#,
+                        bounds.min.x * size as i64,
+                        bounds.min.y * size as i64,
+                        bounds.width() * size as i64,
+                        bounds.height() * size as i64
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut bytes = Vec::new();
+        self.write_png(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    pub fn write_png<W: std::io::Write>(&self, w: W) -> Result<(), png::EncodingError> {
+        let pixels = self.rasterize();
+        let mut encoder = png::Encoder::new(w, self.width(), self.height());
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels.data)?;
+        writer.finish()
+    }
+
+    fn rasterize(&self) -> Canvas {
+        let size = self.cell_size as i64;
+        let mut canvas = Canvas::new(self.width(), self.height());
+
+        for (pos, class) in self.classes.iter() {
+            let (x, y) = (pos.x * size, pos.y * size);
+            canvas.fill_rect(x, y, size, size, class.colour());
+            canvas.outline_rect(x, y, size, size, 1, Colour::GRID);
+        }
+
+        for overlay in &self.overlays {
+            match overlay {
+                Overlay::Path { cells, colour } => {
+                    let thickness = size / 4 + 1;
+                    for pair in cells.windows(2) {
+                        let (x0, y0) = self.centre(pair[0]);
+                        let (x1, y1) = self.centre(pair[1]);
+                        canvas.line(x0, y0, x1, y1, thickness, *colour);
+                    }
+                    if let [only] = cells.as_slice() {
+                        let (x, y) = self.centre(*only);
+                        canvas.line(x, y, x, y, thickness, *colour);
+                    }
+                }
+                Overlay::Area { bounds, colour } => {
+                    canvas.outline_rect(
+                        bounds.min.x * size,
+                        bounds.min.y * size,
+                        bounds.width() * size,
+                        bounds.height() * size,
+                        2,
+                        *colour,
+                    );
+                }
+            }
+        }
+
+        canvas
+    }
+
+    fn centre(&self, pos: Vec2) -> (i64, i64) {
+        let size = self.cell_size as i64;
+        (pos.x * size + size / 2, pos.y * size + size / 2)
+    }
+}
+
+impl std::fmt::Display for Colour {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+fn escape(c: char) -> String {
+    match c {
+        '&' => "&amp;".into(),
+        '<' => "&lt;".into(),
+        '>' => "&gt;".into(),
+        '"' => "&quot;".into(),
+        '\'' => "&apos;".into(),
+        c => c.to_string(),
+    }
+}
+
+// An RGB pixel buffer with just enough drawing primitives for `Picture`
+struct Canvas {
+    width: i64,
+    height: i64,
+    data: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Canvas {
+        Canvas {
+            width: width as i64,
+            height: height as i64,
+            data: vec![0xff; width as usize * height as usize * 3],
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64, colour: Colour) {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return;
+        }
+        let idx = (y * self.width + x) as usize * 3;
+        self.data[idx..idx + 3].copy_from_slice(&[colour.0, colour.1, colour.2]);
+    }
+
+    fn fill_rect(&mut self, x: i64, y: i64, w: i64, h: i64, colour: Colour) {
+        for py in y..y + h {
+            for px in x..x + w {
+                self.set(px, py, colour);
+            }
+        }
+    }
+
+    fn outline_rect(&mut self, x: i64, y: i64, w: i64, h: i64, thickness: i64, colour: Colour) {
+        self.fill_rect(x, y, w, thickness, colour);
+        self.fill_rect(x, y + h - thickness, w, thickness, colour);
+        self.fill_rect(x, y, thickness, h, colour);
+        self.fill_rect(x + w - thickness, y, thickness, h, colour);
+    }
+
+    // Bresenham's line, stamping a square of side `thickness` at every step
+    fn line(&mut self, x0: i64, y0: i64, x1: i64, y1: i64, thickness: i64, colour: Colour) {
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+        let (mut x, mut y, mut err) = (x0, y0, dx + dy);
+        let half = thickness / 2;
+
+        loop {
+            self.fill_rect(x - half, y - half, thickness, thickness, colour);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::geometry::{Bounds, Vec2};
+use rand::{rngs::StdRng, seq::IndexedRandom, Rng, SeedableRng};
+use std::collections::HashMap;
+
+// Builds random engine schematics, keeping track of what it placed so the answers can be worked
+// out without going through `Schematic`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    width: usize,
+    height: usize,
+    digit_density: f64,
+    max_digits: u32,
+    symbols: Vec<char>,
+    symbol_density: f64,
+    gear_probability: f64,
+    seed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub lines: Vec<String>,
+    // The sum of every number touching a symbol
+    pub part_sum: Option<u64>,
+    // The sum of the products of the numbers around every `*` touching exactly two
+    pub gear_ratio_sum: Option<u64>,
+}
+
+// A number as it was written, leading zeroes included
+struct Placed {
+    start: Vec2,
+    text: String,
+}
+
+impl Generator {
+    pub fn new(width: usize, height: usize) -> Generator {
+        Generator {
+            width,
+            height,
+            digit_density: 0.15,
+            max_digits: 3,
+            symbols: vec!['#', '$', '%', '&', '+', '-', '/', '=', '@'],
+            symbol_density: 0.05,
+            gear_probability: 0.3,
+            seed: 0,
+        }
+    }
+
+    // The chance of a number starting at any free cell
+    pub fn digit_density(mut self, density: f64) -> Generator {
+        self.digit_density = density.clamp(0.0, 1.0);
+        self
+    }
+
+    // Numbers are between 1 and `max_digits` long. At most 19, so every number fits in 64 bits.
+    // Past 9 digits the answers might not, and are `None` when they don't
+    pub fn max_digits(mut self, max_digits: u32) -> Generator {
+        self.max_digits = max_digits.clamp(1, 19);
+        self
+    }
+
+    // Symbols other than gears. They should all be symbols for the config the output is parsed
+    // with
+    pub fn symbols<I: IntoIterator<Item = char>>(mut self, symbols: I) -> Generator {
+        self.symbols = symbols.into_iter().collect();
+        self
+    }
+
+    // The chance of a symbol at any free cell that isn't a number
+    pub fn symbol_density(mut self, density: f64) -> Generator {
+        self.symbol_density = density.clamp(0.0, 1.0);
+        self
+    }
+
+    // The chance of a symbol being a `*`
+    pub fn gear_probability(mut self, probability: f64) -> Generator {
+        self.gear_probability = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Generator {
+        self.seed = seed;
+        self
+    }
+
+    pub fn generate(&self) -> Generated {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut numbers = Vec::new();
+        let mut symbols = HashMap::new();
+
+        let lines = (0..self.height)
+            .map(|y| {
+                let mut line = String::with_capacity(self.width);
+                let mut x = 0;
+                while x < self.width {
+                    if rng.random_bool(self.digit_density) {
+                        let len = rng.random_range(1..=self.max_digits as usize);
+                        let len = len.min(self.width - x);
+                        let text: String = (0..len)
+                            .map(|_| char::from(b'0' + rng.random_range(0..10)))
+                            .collect();
+                        line.push_str(&text);
+                        numbers.push(Placed {
+                            start: Vec2::new(x as i64, y as i64),
+                            text,
+                        });
+                        x += len;
+                        if x == self.width {
+                            break;
+                        }
+                    }
+
+                    // Numbers need something else after them, or they would run into the next one
+                    let c = self.non_digit(&mut rng);
+                    if c != '.' {
+                        symbols.insert(Vec2::new(x as i64, y as i64), c);
+                    }
+                    line.push(c);
+                    x += 1;
+                }
+                line
+            })
+            .collect();
+
+        let (part_sum, gear_ratio_sum) = answers(&numbers, &symbols);
+        Generated {
+            lines,
+            part_sum,
+            gear_ratio_sum,
+        }
+    }
+
+    fn non_digit(&self, rng: &mut StdRng) -> char {
+        if !rng.random_bool(self.symbol_density) {
+            '.'
+        } else if rng.random_bool(self.gear_probability) {
+            '*'
+        } else {
+            self.symbols.choose(rng).copied().unwrap_or('*')
+        }
+    }
+}
+
+impl Generated {
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().map(|line| line.as_str())
+    }
+}
+
+// Checks every cell around every number against the symbols that were placed
+fn answers(numbers: &[Placed], symbols: &HashMap<Vec2, char>) -> (Option<u64>, Option<u64>) {
+    let mut part_sum = Some(0u64);
+    let mut gear_numbers: HashMap<Vec2, Vec<u64>> = HashMap::new();
+
+    for number in numbers {
+        let value: u64 = number.text.parse().expect("numbers are at most 19 digits");
+        let around = Bounds::new(
+            number.start - Vec2::new(1, 1),
+            number.start + Vec2::new(number.text.len() as i64, 1),
+        );
+
+        let mut touches_symbol = false;
+        for pos in around.cells() {
+            match symbols.get(&pos) {
+                Some('*') => {
+                    touches_symbol = true;
+                    gear_numbers.entry(pos).or_default().push(value);
+                }
+                Some(_) => touches_symbol = true,
+                None => {}
+            }
+        }
+        if touches_symbol {
+            part_sum = part_sum.and_then(|sum| sum.checked_add(value));
+        }
+    }
+
+    let gear_ratio_sum = gear_numbers
+        .values()
+        .filter(|values| values.len() == 2)
+        .try_fold(0u64, |sum, values| {
+            sum.checked_add(values[0].checked_mul(values[1])?)
+        });
+    (part_sum, gear_ratio_sum)
+}
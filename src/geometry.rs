@@ -0,0 +1,441 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    pub fn manhattan(&self, other: Vec2) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    // Distance when diagonal steps are allowed, i.e. how many king moves apart two points are
+    pub fn chebyshev(&self, other: Vec2) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    // Rotations are by 90 degrees around the origin, as seen on screen with `y` pointing down
+    pub fn rotate_left(&self) -> Vec2 {
+        Vec2::new(self.y, -self.x)
+    }
+
+    pub fn rotate_right(&self) -> Vec2 {
+        Vec2::new(-self.y, self.x)
+    }
+
+    pub fn signum(&self) -> Vec2 {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+}
+
+// The 8 compass directions, in clockwise order. `y` grows downwards, so `N` is `[0, -1]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    pub fn offset(&self) -> Vec2 {
+        match self {
+            Direction::N => Vec2::new(0, -1),
+            Direction::NE => Vec2::new(1, -1),
+            Direction::E => Vec2::new(1, 0),
+            Direction::SE => Vec2::new(1, 1),
+            Direction::S => Vec2::new(0, 1),
+            Direction::SW => Vec2::new(-1, 1),
+            Direction::W => Vec2::new(-1, 0),
+            Direction::NW => Vec2::new(-1, -1),
+        }
+    }
+
+    // Turns clockwise by `eighths` of a full turn. Negative values turn counter-clockwise
+    pub fn turn(&self, eighths: i64) -> Direction {
+        Direction::ALL[(*self as i64 + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_left(&self) -> Direction {
+        self.turn(-2)
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        self.turn(2)
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.turn(4)
+    }
+
+    pub fn is_cardinal(&self) -> bool {
+        (*self as usize).is_multiple_of(2)
+    }
+}
+
+impl std::fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}]", self.x, self.y)
+    }
+}
+
+impl std::ops::Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl std::ops::AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl std::ops::Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl std::ops::SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl std::ops::Mul<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Vec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl std::ops::MulAssign<i64> for Vec2 {
+    fn mul_assign(&mut self, rhs: i64) {
+        *self = *self * rhs;
+    }
+}
+
+impl std::ops::Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Self::Output {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl From<(i64, i64)> for Vec2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Vec2::new(x, y)
+    }
+}
+
+impl From<Vec2> for (i64, i64) {
+    fn from(v: Vec2) -> Self {
+        (v.x, v.y)
+    }
+}
+
+impl From<Direction> for Vec2 {
+    fn from(dir: Direction) -> Self {
+        dir.offset()
+    }
+}
+
+// Anything that can be bounded by a `Bounds`, i.e. points whose coordinates can be compared one
+// axis at a time
+pub trait Point: Copy + Eq {
+    fn component_min(self, other: Self) -> Self;
+    fn component_max(self, other: Self) -> Self;
+    // True if every coordinate is less than or equal to the matching one in `other`
+    fn all_le(self, other: Self) -> bool;
+}
+
+impl Point for Vec2 {
+    fn component_min(self, other: Self) -> Self {
+        Vec2::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    fn component_max(self, other: Self) -> Self {
+        Vec2::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    fn all_le(self, other: Self) -> bool {
+        self.x <= other.x && self.y <= other.y
+    }
+}
+
+// A point with any number of dimensions, for puzzles that go beyond the plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VecN<const N: usize>(pub [i64; N]);
+
+pub type Vec3 = VecN<3>;
+pub type Vec4 = VecN<4>;
+
+impl<const N: usize> VecN<N> {
+    pub const ZERO: VecN<N> = VecN([0; N]);
+
+    pub fn manhattan(&self, other: VecN<N>) -> i64 {
+        self.0.iter().zip(other.0).map(|(a, b)| (a - b).abs()).sum()
+    }
+
+    pub fn chebyshev(&self, other: VecN<N>) -> i64 {
+        self.0
+            .iter()
+            .zip(other.0)
+            .map(|(a, b)| (a - b).abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn signum(&self) -> VecN<N> {
+        VecN(self.0.map(i64::signum))
+    }
+
+    // The 2 * N points one step away along a single axis
+    pub fn orthogonal_neighbours(&self) -> impl Iterator<Item = VecN<N>> + '_ {
+        (0..N).flat_map(move |axis| {
+            [-1, 1].into_iter().map(move |delta| {
+                let mut neighbour = *self;
+                neighbour.0[axis] += delta;
+                neighbour
+            })
+        })
+    }
+
+    // The 3^N - 1 points whose coordinates all differ by at most one, diagonals included
+    pub fn neighbours(&self) -> impl Iterator<Item = VecN<N>> + '_ {
+        (0..3usize.pow(N as u32)).filter_map(move |mut idx| {
+            let mut neighbour = *self;
+            for coord in neighbour.0.iter_mut() {
+                *coord += (idx % 3) as i64 - 1;
+                idx /= 3;
+            }
+            (neighbour != *self).then_some(neighbour)
+        })
+    }
+}
+
+impl VecN<3> {
+    pub fn new(x: i64, y: i64, z: i64) -> Vec3 {
+        VecN([x, y, z])
+    }
+
+    pub fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> i64 {
+        self.0[2]
+    }
+}
+
+impl<const N: usize> Point for VecN<N> {
+    fn component_min(self, other: Self) -> Self {
+        VecN(std::array::from_fn(|i| self.0[i].min(other.0[i])))
+    }
+
+    fn component_max(self, other: Self) -> Self {
+        VecN(std::array::from_fn(|i| self.0[i].max(other.0[i])))
+    }
+
+    fn all_le(self, other: Self) -> bool {
+        self.0.iter().zip(other.0).all(|(a, b)| *a <= b)
+    }
+}
+
+impl<const N: usize> std::fmt::Display for VecN<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (idx, coord) in self.0.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{coord}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<const N: usize> std::ops::Add for VecN<N> {
+    type Output = VecN<N>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        VecN(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<const N: usize> std::ops::AddAssign for VecN<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> std::ops::Sub for VecN<N> {
+    type Output = VecN<N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        VecN(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<const N: usize> std::ops::SubAssign for VecN<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> std::ops::Mul<i64> for VecN<N> {
+    type Output = VecN<N>;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        VecN(self.0.map(|coord| coord * rhs))
+    }
+}
+
+impl<const N: usize> std::ops::MulAssign<i64> for VecN<N> {
+    fn mul_assign(&mut self, rhs: i64) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const N: usize> std::ops::Neg for VecN<N> {
+    type Output = VecN<N>;
+
+    fn neg(self) -> Self::Output {
+        VecN(self.0.map(|coord| -coord))
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for VecN<N> {
+    fn from(coords: [i64; N]) -> Self {
+        VecN(coords)
+    }
+}
+
+impl From<(i64, i64, i64)> for Vec3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl From<Vec2> for VecN<2> {
+    fn from(v: Vec2) -> Self {
+        VecN([v.x, v.y])
+    }
+}
+
+impl From<VecN<2>> for Vec2 {
+    fn from(v: VecN<2>) -> Self {
+        Vec2::new(v.0[0], v.0[1])
+    }
+}
+
+// An axis-aligned box, with both corners included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds<P> {
+    pub min: P,
+    pub max: P,
+}
+
+impl<P: Point> Bounds<P> {
+    // The smallest box holding both corners, whichever order they are given in
+    pub fn new(a: P, b: P) -> Bounds<P> {
+        Bounds {
+            min: a.component_min(b),
+            max: a.component_max(b),
+        }
+    }
+
+    pub fn from_points<I: IntoIterator<Item = P>>(points: I) -> Option<Bounds<P>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = Bounds::new(first, first);
+        for point in points {
+            bounds.extend(point);
+        }
+        Some(bounds)
+    }
+
+    pub fn contains(&self, point: P) -> bool {
+        self.min.all_le(point) && point.all_le(self.max)
+    }
+
+    pub fn extend(&mut self, point: P) {
+        self.min = self.min.component_min(point);
+        self.max = self.max.component_max(point);
+    }
+
+    pub fn union(&self, other: &Bounds<P>) -> Bounds<P> {
+        Bounds {
+            min: self.min.component_min(other.min),
+            max: self.max.component_max(other.max),
+        }
+    }
+}
+
+impl Bounds<Vec2> {
+    pub fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    // Every position inside the box, row by row
+    pub fn cells(&self) -> impl Iterator<Item = Vec2> {
+        let Bounds { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Vec2::new(x, y)))
+    }
+}
+
+impl<const N: usize> Bounds<VecN<N>> {
+    // Number of points inside the box
+    pub fn volume(&self) -> i64 {
+        self.min
+            .0
+            .iter()
+            .zip(self.max.0)
+            .map(|(min, max)| max - min + 1)
+            .product()
+    }
+}
+
+impl<P: std::fmt::Display> std::fmt::Display for Bounds<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..={}", self.min, self.max)
+    }
+}
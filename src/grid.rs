@@ -0,0 +1,191 @@
+use crate::geometry::{Direction, Vec2};
+use std::iter::Iterator;
+
+// A dense, rectangular grid of cells stored in row-major order. Positions are `Vec2`s with `x`
+// being the column and `y` the row, both starting at 0 in the top left corner
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GridError {
+    #[error("row {row} has {found} cells, expected {expected}")]
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, GridError> {
+        let height = rows.len();
+        let width = rows.first().map(|row| row.len()).unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * height);
+        for (row_idx, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::RaggedRow {
+                    row: row_idx,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            cells.extend(row);
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && pos.x < self.width as i64 && pos.y < self.height as i64
+    }
+
+    pub fn get(&self, pos: Vec2) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2) -> Option<&mut T> {
+        self.index_of(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    // Iterates over every cell along with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+        let width = self.width.max(1);
+        self.cells.iter().enumerate().map(move |(idx, cell)| {
+            let pos = Vec2::new((idx % width) as i64, (idx / width) as i64);
+            (pos, cell)
+        })
+    }
+
+    // The up to 4 orthogonally adjacent cells that are inside the grid
+    pub fn neighbours4(&self, pos: Vec2) -> impl Iterator<Item = (Vec2, &T)> {
+        self.neighbours_in(pos, &Direction::CARDINAL)
+    }
+
+    // The up to 8 orthogonally and diagonally adjacent cells that are inside the grid
+    pub fn neighbours8(&self, pos: Vec2) -> impl Iterator<Item = (Vec2, &T)> {
+        self.neighbours_in(pos, &Direction::ALL)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        if x < self.width {
+            Some(self.cells.iter().skip(x).step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // The cells one step away in each of `directions` that are inside the grid
+    pub fn neighbours_in<'a>(
+        &'a self,
+        pos: Vec2,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = (Vec2, &'a T)> {
+        directions.iter().filter_map(move |dir| {
+            let pos = pos + dir.offset();
+            self.get(pos).map(|cell| (pos, cell))
+        })
+    }
+
+    fn index_of(&self, pos: Vec2) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.y as usize * self.width + pos.x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl Grid<char> {
+    // Builds a grid with one row per line. Rows shorter than the longest line are padded with
+    // `pad`, so ragged input still produces a rectangular grid
+    pub fn parse<'a, I: Iterator<Item = &'a str>>(lines: I, pad: char) -> Grid<char> {
+        let mut rows: Vec<Vec<char>> = lines.map(|line| line.chars().collect()).collect();
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(width, pad);
+        }
+
+        Grid::from_rows(rows).expect("rows were padded to the same width")
+    }
+}
+
+impl<T> std::ops::Index<Vec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos} is outside of the grid"))
+    }
+}
+
+impl<T> std::ops::IndexMut<Vec2> for Grid<T> {
+    fn index_mut(&mut self, pos: Vec2) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos} is outside of the grid"))
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+use crate::geometry::Vec2;
+use std::collections::HashMap;
+
+// A cell of a hexagonal grid in axial coordinates. The third cube coordinate is `s`, and the three
+// always add up to 0. Hexes are pointy-topped, so rows run left to right and `r` is the row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+// The six neighbouring directions, clockwise from east
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HexDirection {
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+    NE,
+}
+
+// How rows of hexes are laid out as rows of a rectangle. Every other row is shifted half a cell to
+// the right: the odd ones for `OddR` and the even ones for `EvenR`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OffsetLayout {
+    #[default]
+    OddR,
+    EvenR,
+}
+
+// Hexes read from text, along with the layout they were written in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexGrid<T> {
+    cells: HashMap<Hex, T>,
+    layout: OffsetLayout,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HexParseError {
+    #[error("'{c}' at line {line}, column {column} is between two cells")]
+    Misaligned { line: usize, column: usize, c: char },
+}
+
+impl Hex {
+    pub const ZERO: Hex = Hex { q: 0, r: 0 };
+
+    pub fn new(q: i64, r: i64) -> Hex {
+        Hex { q, r }
+    }
+
+    // Fails unless the coordinates add up to 0
+    pub fn from_cube(q: i64, r: i64, s: i64) -> Option<Hex> {
+        (q + r + s == 0).then_some(Hex { q, r })
+    }
+
+    pub fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    pub fn neighbour(&self, dir: HexDirection) -> Hex {
+        *self + dir.offset()
+    }
+
+    pub fn neighbours(&self) -> impl Iterator<Item = Hex> {
+        let hex = *self;
+        HexDirection::ALL.iter().map(move |dir| hex.neighbour(*dir))
+    }
+
+    // Number of steps from the origin
+    pub fn length(&self) -> i64 {
+        self.q.abs().max(self.r.abs()).max(self.s().abs())
+    }
+
+    pub fn distance(&self, other: Hex) -> i64 {
+        (*self - other).length()
+    }
+
+    // Turns 60 degrees clockwise around the origin
+    pub fn rotate_right(&self) -> Hex {
+        Hex::new(-self.r, -self.s())
+    }
+
+    // Turns 60 degrees counterclockwise around the origin
+    pub fn rotate_left(&self) -> Hex {
+        Hex::new(-self.s(), -self.q)
+    }
+
+    // Turns `sixths` sixths of a full turn clockwise around `centre`. Negative turns go
+    // counterclockwise
+    pub fn rotate_around(&self, centre: Hex, sixths: i64) -> Hex {
+        let mut offset = *self - centre;
+        for _ in 0..sixths.rem_euclid(6) {
+            offset = offset.rotate_right();
+        }
+        centre + offset
+    }
+
+    // The hexes exactly `radius` steps away, clockwise from the one furthest west
+    pub fn ring(&self, radius: u64) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+
+        let mut hex = *self + HexDirection::W.offset() * radius as i64;
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        // Going clockwise from the west corner, the first side heads north east
+        for dir in [
+            HexDirection::NE,
+            HexDirection::E,
+            HexDirection::SE,
+            HexDirection::SW,
+            HexDirection::W,
+            HexDirection::NW,
+        ] {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = hex.neighbour(dir);
+            }
+        }
+        ring
+    }
+
+    // The hexes at most `radius` steps away, ring by ring from this one outwards
+    pub fn spiral(&self, radius: u64) -> Vec<Hex> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+
+    // The column and row of this hex when laid out in rows
+    pub fn to_offset(&self, layout: OffsetLayout) -> Vec2 {
+        let shift = match layout {
+            OffsetLayout::OddR => (self.r - (self.r & 1)) / 2,
+            OffsetLayout::EvenR => (self.r + (self.r & 1)) / 2,
+        };
+        Vec2::new(self.q + shift, self.r)
+    }
+
+    pub fn from_offset(pos: Vec2, layout: OffsetLayout) -> Hex {
+        let shift = match layout {
+            OffsetLayout::OddR => (pos.y - (pos.y & 1)) / 2,
+            OffsetLayout::EvenR => (pos.y + (pos.y & 1)) / 2,
+        };
+        Hex::new(pos.x - shift, pos.y)
+    }
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::E,
+        HexDirection::SE,
+        HexDirection::SW,
+        HexDirection::W,
+        HexDirection::NW,
+        HexDirection::NE,
+    ];
+
+    pub fn offset(&self) -> Hex {
+        match self {
+            HexDirection::E => Hex::new(1, 0),
+            HexDirection::SE => Hex::new(0, 1),
+            HexDirection::SW => Hex::new(-1, 1),
+            HexDirection::W => Hex::new(-1, 0),
+            HexDirection::NW => Hex::new(0, -1),
+            HexDirection::NE => Hex::new(1, -1),
+        }
+    }
+
+    // Turns `sixths` sixths of a full turn clockwise. Negative turns go counterclockwise
+    pub fn turn(&self, sixths: i64) -> HexDirection {
+        let idx = HexDirection::ALL
+            .iter()
+            .position(|dir| dir == self)
+            .expect("every direction is in ALL") as i64;
+        HexDirection::ALL[(idx + sixths).rem_euclid(6) as usize]
+    }
+
+    pub fn opposite(&self) -> HexDirection {
+        self.turn(3)
+    }
+}
+
+impl OffsetLayout {
+    // Whether `row` is indented by half a cell
+    pub fn is_shifted(&self, row: i64) -> bool {
+        match self {
+            OffsetLayout::OddR => row.rem_euclid(2) == 1,
+            OffsetLayout::EvenR => row.rem_euclid(2) == 0,
+        }
+    }
+}
+
+impl<T> HexGrid<T> {
+    pub fn layout(&self) -> OffsetLayout {
+        self.layout
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, hex: Hex) -> bool {
+        self.cells.contains_key(&hex)
+    }
+
+    pub fn get(&self, hex: Hex) -> Option<&T> {
+        self.cells.get(&hex)
+    }
+
+    pub fn insert(&mut self, hex: Hex, value: T) -> Option<T> {
+        self.cells.insert(hex, value)
+    }
+
+    // Every cell along with its hex, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Hex, &T)> {
+        self.cells.iter().map(|(hex, cell)| (*hex, cell))
+    }
+
+    // The up to 6 adjacent cells that are in the grid
+    pub fn neighbours(&self, hex: Hex) -> impl Iterator<Item = (Hex, &T)> {
+        hex.neighbours()
+            .filter_map(|hex| self.get(hex).map(|cell| (hex, cell)))
+    }
+}
+
+impl HexGrid<char> {
+    // Reads rows written with a space between cells and shifted rows indented by one space:
+    //
+    //   a b c
+    //    d e f
+    //   g h i
+    //
+    // is `OddR`. Spaces are gaps, and every other character is a cell
+    pub fn parse<'a, I: Iterator<Item = &'a str>>(
+        lines: I,
+        layout: OffsetLayout,
+    ) -> Result<HexGrid<char>, HexParseError> {
+        let mut cells = HashMap::new();
+        for (y, line) in lines.enumerate() {
+            let indent = layout.is_shifted(y as i64) as usize;
+            for (column, c) in line.chars().enumerate() {
+                if c == ' ' {
+                    continue;
+                }
+                if column < indent || !(column - indent).is_multiple_of(2) {
+                    return Err(HexParseError::Misaligned { line: y, column, c });
+                }
+                let pos = Vec2::new(((column - indent) / 2) as i64, y as i64);
+                cells.insert(Hex::from_offset(pos, layout), c);
+            }
+        }
+        Ok(HexGrid { cells, layout })
+    }
+}
+
+impl<T> std::ops::Index<Hex> for HexGrid<T> {
+    type Output = T;
+
+    fn index(&self, hex: Hex) -> &Self::Output {
+        self.get(hex)
+            .unwrap_or_else(|| panic!("{hex} is not in the grid"))
+    }
+}
+
+// Writes the cells back out the way `parse` reads them, from row 0 and column 0. Anything above
+// or left of that isn't drawn
+impl<T: std::fmt::Display> std::fmt::Display for HexGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut rows: Vec<Vec<(i64, &T)>> = Vec::new();
+        for (hex, cell) in self.iter() {
+            let pos = hex.to_offset(self.layout);
+            if pos.x < 0 || pos.y < 0 {
+                continue;
+            }
+            if rows.len() <= pos.y as usize {
+                rows.resize_with(pos.y as usize + 1, Vec::new);
+            }
+            rows[pos.y as usize].push((pos.x, cell));
+        }
+
+        for (y, row) in rows.iter_mut().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            row.sort_by_key(|(x, _)| *x);
+            let indent = self.layout.is_shifted(y as i64) as usize;
+            let mut line = String::new();
+            for (x, cell) in row.iter() {
+                let column = indent + 2 * *x as usize;
+                while line.chars().count() < column {
+                    line.push(' ');
+                }
+                line.push_str(&cell.to_string());
+            }
+            write!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::ops::Add for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Hex) -> Self::Output {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl std::ops::Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Hex) -> Self::Output {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl std::ops::Mul<i64> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl std::ops::Neg for Hex {
+    type Output = Hex;
+
+    fn neg(self) -> Self::Output {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+impl std::ops::AddAssign for Hex {
+    fn add_assign(&mut self, rhs: Hex) {
+        *self = *self + rhs;
+    }
+}
+
+impl std::ops::SubAssign for Hex {
+    fn sub_assign(&mut self, rhs: Hex) {
+        *self = *self - rhs;
+    }
+}
+
+impl From<HexDirection> for Hex {
+    fn from(dir: HexDirection) -> Self {
+        dir.offset()
+    }
+}
+
+impl std::fmt::Display for Hex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}, {}]", self.q, self.r, self.s())
+    }
+}
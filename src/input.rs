@@ -0,0 +1,140 @@
+use flate2::bufread::MultiGzDecoder;
+use memmap2::Mmap;
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufRead, BufReader, Cursor},
+    path::Path,
+};
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+// Opens a file as a buffered reader, transparently decompressing it if needed. With `mmap` set the
+// file is memory-mapped instead of read through the usual syscalls, which is much faster for very
+// large inputs
+pub fn open(path: &Path, mmap: bool) -> std::io::Result<Box<dyn BufRead>> {
+    let file = File::open(path)?;
+    if mmap {
+        // SAFETY: the mapping is read-only and puzzle inputs are not expected to be modified while
+        // we are reading them
+        let map = unsafe { Mmap::map(&file)? };
+        decompress(Cursor::new(map))
+    } else {
+        decompress(BufReader::new(file))
+    }
+}
+
+pub fn from_string(s: &str) -> Box<dyn BufRead> {
+    Box::new(Cursor::new(s.as_bytes().to_vec()))
+}
+
+// Wraps the reader in a gzip or zstd decoder if it starts with the matching magic bytes, and
+// returns it untouched otherwise
+pub fn decompress<R: BufRead + 'static>(mut reader: R) -> std::io::Result<Box<dyn BufRead>> {
+    let header = reader.fill_buf()?;
+    if header.starts_with(GZIP_MAGIC) {
+        Ok(Box::new(BufReader::new(MultiGzDecoder::new(reader))))
+    } else if header.starts_with(ZSTD_MAGIC) {
+        Ok(Box::new(BufReader::new(zstd::Decoder::with_buffer(
+            reader,
+        )?)))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+// Lazily yields the lines of one or more readers, in sequence, with the line terminator (`\n` or
+// `\r\n`) removed. A line never spans two readers, even if one doesn't end in a newline
+pub struct Lines<R> {
+    readers: VecDeque<R>,
+    trim: bool,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Lines<R> {
+        Lines::chain(vec![reader])
+    }
+
+    pub fn chain(readers: Vec<R>) -> Lines<R> {
+        Lines {
+            readers: readers.into(),
+            trim: false,
+        }
+    }
+
+    // Also strips any leading and trailing whitespace from every line
+    pub fn trimmed(mut self) -> Lines<R> {
+        self.trim = true;
+        self
+    }
+
+    pub fn paragraphs(self) -> Paragraphs<Lines<R>> {
+        Paragraphs::new(self)
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = std::io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = String::new();
+        let reader = self.readers.front_mut()?;
+        match reader.read_line(&mut line) {
+            Ok(0) => {
+                self.readers.pop_front();
+                self.next()
+            }
+            Ok(_) => {
+                if self.trim {
+                    Some(Ok(line.trim().into()))
+                } else {
+                    // Only the terminator goes, so a `\r` that is part of the line is kept
+                    if let Some(stripped) = line.strip_suffix('\n') {
+                        let len = stripped.strip_suffix('\r').unwrap_or(stripped).len();
+                        line.truncate(len);
+                    }
+                    Some(Ok(line))
+                }
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+// Groups lines into blocks separated by one or more blank lines, which is how most multi-section
+// puzzle inputs are laid out
+pub struct Paragraphs<I> {
+    lines: I,
+}
+
+impl<I: Iterator<Item = std::io::Result<String>>> Paragraphs<I> {
+    pub fn new(lines: I) -> Paragraphs<I> {
+        Paragraphs { lines }
+    }
+}
+
+impl<I: Iterator<Item = std::io::Result<String>>> Iterator for Paragraphs<I> {
+    type Item = std::io::Result<Vec<String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut paragraph = Vec::new();
+        for line in self.lines.by_ref() {
+            match line {
+                Ok(line) if line.trim().is_empty() => {
+                    if !paragraph.is_empty() {
+                        return Some(Ok(paragraph));
+                    }
+                }
+                Ok(line) => paragraph.push(line),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        if paragraph.is_empty() {
+            None
+        } else {
+            Some(Ok(paragraph))
+        }
+    }
+}
@@ -1,37 +1,114 @@
+use cache::{Answers, InputCache};
 use clap::Parser;
+use color_eyre::eyre::Result;
+use input::Lines;
 use std::{
-    fs::File,
-    io::{BufRead, BufReader, Read},
+    io::{BufRead, BufReader},
     path::PathBuf,
 };
 
 pub mod block_game;
+pub mod cache;
+pub mod components;
+pub mod diff;
 pub mod engine;
+pub mod export;
+pub mod generator;
+pub mod geometry;
+pub mod grid;
+pub mod hex;
+pub mod input;
+pub mod render;
+pub mod report;
+pub mod scaffold;
+pub mod search;
+pub mod sparse;
+pub mod spatial;
+pub mod topology;
+pub mod transform;
 pub mod trie;
 
 #[derive(Parser, Debug)]
 pub struct Opt {
+    /// Input files, read in sequence. Gzip and zstd compressed files are decompressed on the fly.
+    /// If none are given, input is read from stdin
     #[clap(index = 1)]
-    pub file_name: Option<PathBuf>,
+    pub file_names: Vec<PathBuf>,
+
+    /// Use this string as the puzzle input instead of reading any files
+    #[clap(long, conflicts_with = "file_names")]
+    pub input_string: Option<String>,
+
+    /// Read the cached input for this day from the input directory
+    #[clap(long, conflicts_with_all = ["file_names", "input_string"])]
+    pub day: Option<u32>,
+
+    /// Use the cached example input for --day instead, and check the answers against it
+    #[clap(long, requires = "day")]
+    pub example: bool,
+
+    /// Directory holding cached puzzle inputs. Defaults to the user cache directory
+    #[clap(long, env = "AOC_INPUT_DIR")]
+    pub input_dir: Option<PathBuf>,
+
+    /// Memory-map input files instead of reading them
+    #[clap(long)]
+    pub mmap: bool,
+
+    #[clap(long, value_enum, default_value_t = report::Format::Text)]
+    pub format: report::Format,
+}
+
+impl Opt {
+    pub fn cache(&self) -> InputCache {
+        InputCache::new(self.input_dir.clone())
+    }
+
+    // Expected answers apply only when running on a cached example
+    pub fn expected_answers(&self) -> Result<Option<Answers>> {
+        match self.day {
+            Some(day) if self.example => self.cache().expected_answers(day),
+            _ => Ok(None),
+        }
+    }
 }
 
 pub fn read_lines(opt: &Opt) -> std::io::Result<Vec<String>> {
-    if let Some(file_name) = &opt.file_name {
-        read_lines_impl(File::open(file_name)?)
+    lines(opt)?.collect()
+}
+
+pub fn lines(opt: &Opt) -> std::io::Result<Lines<Box<dyn BufRead>>> {
+    let readers = if let Some(input) = &opt.input_string {
+        vec![input::from_string(input)]
+    } else if !opt.file_names.is_empty() {
+        opt.file_names
+            .iter()
+            .map(|file_name| input::open(file_name, opt.mmap))
+            .collect::<std::io::Result<Vec<_>>>()?
+    } else if let Some(day) = opt.day {
+        vec![open_cached(opt, day)?]
     } else {
-        read_lines_impl(std::io::stdin())
-    }
+        vec![input::decompress(BufReader::new(std::io::stdin()))?]
+    };
+    Ok(Lines::chain(readers))
 }
 
-fn read_lines_impl<R: Read>(r: R) -> std::io::Result<Vec<String>> {
-    let mut reader = BufReader::new(r);
-    let mut lines = Vec::new();
-    loop {
-        let mut line = String::new();
-        if reader.read_line(&mut line)? == 0 {
-            break;
-        }
-        lines.push(line);
+// Fails with a hint on how to fill the cache, rather than a bare "file not found"
+fn open_cached(opt: &Opt, day: u32) -> std::io::Result<Box<dyn BufRead>> {
+    let path = opt.cache().path(day, opt.example);
+    if !path.is_file() {
+        let (what, flag) = if opt.example {
+            ("example", " --example")
+        } else {
+            ("input", "")
+        };
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!(
+                "no cached {what} for day {day} at {}, run `aoc inputs add --day {day}{flag} <file>` first",
+                path.display()
+            ),
+        ));
     }
-    Ok(lines)
+    input::open(&path, opt.mmap)
 }
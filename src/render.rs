@@ -0,0 +1,77 @@
+use crate::engine::{Gear, Part, Schematic};
+use clap::ValueEnum;
+use std::{collections::HashMap, fmt::Write};
+
+const RESET: &str = "\x1b[0m";
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const GEAR: &str = "\x1b[1;33m";
+const SYMBOL: &str = "\x1b[1m";
+
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RenderStyle {
+    // Colours valid parts green, invalid parts red and gears yellow
+    #[default]
+    Ansi,
+    // Marks valid parts as `[123]`, invalid parts as `{123}` and gears as `<*>`, for terminals
+    // and files without colour
+    Plain,
+}
+
+// Redraws the schematic grid with its parts and gears highlighted, followed by the list of gears
+// and their ratios
+pub fn render(schematic: &Schematic, style: RenderStyle) -> String {
+    let graph = schematic.graph();
+    let parts: HashMap<_, _> = graph.parts().map(|part| (part.id.start, part)).collect();
+    let mut gears: Vec<Gear> = schematic.get_gears();
+    gears.sort_by_key(|gear| (gear.pos.y, gear.pos.x));
+    let gear_positions: HashMap<_, _> = gears.iter().map(|gear| (gear.pos, gear)).collect();
+
+    let grid = schematic.grid();
+    let mut out = String::new();
+    for (y, row) in grid.rows().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let pos = (x as i64, y as i64).into();
+            if let Some(part) = parts.get(&pos) {
+                let valid = !graph.symbols_adjacent_to(part.id).is_empty();
+                out.push_str(&render_part(row, part, valid, style));
+                x += part.id.len as usize;
+                continue;
+            }
+
+            let c = row[x];
+            let is_gear = gear_positions.contains_key(&pos);
+            let is_symbol = graph.symbol(pos).is_some();
+            match (style, is_gear, is_symbol) {
+                (RenderStyle::Ansi, true, _) => write!(out, "{GEAR}{c}{RESET}").unwrap(),
+                (RenderStyle::Ansi, false, true) => write!(out, "{SYMBOL}{c}{RESET}").unwrap(),
+                (RenderStyle::Plain, true, _) => write!(out, "<{c}>").unwrap(),
+                _ => out.push(c),
+            }
+            x += 1;
+        }
+        out.push('\n');
+    }
+
+    for gear in &gears {
+        let line = format!("Gear {gear}");
+        match style {
+            RenderStyle::Ansi => writeln!(out, "{GEAR}{line}{RESET}").unwrap(),
+            RenderStyle::Plain => writeln!(out, "{line}").unwrap(),
+        }
+    }
+
+    out.trim_end().into()
+}
+
+fn render_part(row: &[char], part: &Part, valid: bool, style: RenderStyle) -> String {
+    let start = part.id.start.x as usize;
+    let text: String = row[start..start + part.id.len as usize].iter().collect();
+    match (style, valid) {
+        (RenderStyle::Ansi, true) => format!("{GREEN}{text}{RESET}"),
+        (RenderStyle::Ansi, false) => format!("{RED}{text}{RESET}"),
+        (RenderStyle::Plain, true) => format!("[{text}]"),
+        (RenderStyle::Plain, false) => format!("{{{text}}}"),
+    }
+}
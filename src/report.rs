@@ -0,0 +1,113 @@
+use crate::cache::Answers;
+use clap::ValueEnum;
+use serde_json::json;
+use std::time::Instant;
+
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+// Collects everything a solver wants to say about a run, so that it can be printed either as
+// human-readable text or as a single JSON object
+#[derive(Debug)]
+pub struct Report {
+    day: u32,
+    start: Instant,
+    answers: Vec<Answer>,
+    details: Vec<String>,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Answer {
+    part: u32,
+    label: String,
+    value: u64,
+}
+
+impl Report {
+    pub fn new(day: u32) -> Report {
+        Report {
+            day,
+            start: Instant::now(),
+            answers: Vec::new(),
+            details: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn answer(&mut self, part: u32, label: &str, value: u64) {
+        self.answers.push(Answer {
+            part,
+            label: label.into(),
+            value,
+        });
+    }
+
+    // Details are only meant for humans, so they get dropped from the JSON output
+    pub fn detail<S: Into<String>>(&mut self, detail: S) {
+        self.details.push(detail.into());
+    }
+
+    pub fn warn<S: Into<String>>(&mut self, warning: S) {
+        self.warnings.push(warning.into());
+    }
+
+    // Flags every answer that doesn't match its expected value, e.g. when running on an example
+    pub fn check(&mut self, expected: &Answers) {
+        let mismatches: Vec<_> = self
+            .answers
+            .iter()
+            .filter_map(|answer| {
+                let want = expected.get(&answer.part)?;
+                (*want != answer.value).then(|| {
+                    format!(
+                        "part {} answer {} does not match the expected {want}",
+                        answer.part, answer.value
+                    )
+                })
+            })
+            .collect();
+        self.warnings.extend(mismatches);
+    }
+
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Text => println!("{self}"),
+            Format::Json => println!("{}", self.to_json()),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let answers: serde_json::Map<_, _> = self
+            .answers
+            .iter()
+            .map(|answer| (format!("part{}", answer.part), json!(answer.value)))
+            .collect();
+
+        json!({
+            "day": self.day,
+            "answers": answers,
+            "elapsed_ms": self.start.elapsed().as_secs_f64() * 1000.,
+            "warnings": self.warnings,
+        })
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for detail in &self.details {
+            writeln!(f, "{detail}")?;
+        }
+        for warning in &self.warnings {
+            writeln!(f, "warning: {warning}")?;
+        }
+        for answer in &self.answers {
+            writeln!(f, "{}: {}", answer.label, answer.value)?;
+        }
+        write!(f, "Elapsed: {:?}", self.start.elapsed())
+    }
+}
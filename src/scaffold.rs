@@ -0,0 +1,129 @@
+use crate::cache::DAYS;
+use color_eyre::eyre::{eyre, Result};
+use std::path::{Path, PathBuf};
+
+const MODULE_TEMPLATE: &str = include_str!("../templates/day_module.rs.tmpl");
+const BIN_TEMPLATE: &str = include_str!("../templates/day_bin.rs.tmpl");
+
+// Words that match the module name pattern but can't be used as a `mod` name
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+// Generates the boilerplate for a new day: a library module with a test stub, a binary that runs
+// it, and the matching `pub mod` line in `lib.rs`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scaffold {
+    root: PathBuf,
+    day: u32,
+    module: String,
+}
+
+impl Scaffold {
+    // The module name ends up in a file path and in `lib.rs`, so anything but a plain lowercase
+    // identifier (`[a-z_][a-z0-9_]*`, not a keyword) is rejected, as is a day outside `DAYS`
+    pub fn new(root: &Path, day: u32, module: Option<String>) -> Result<Scaffold> {
+        if !DAYS.contains(&day) {
+            return Err(eyre!(
+                "day {day} is out of range, expected {}..={}",
+                DAYS.start(),
+                DAYS.end()
+            ));
+        }
+        let module = module.unwrap_or_else(|| format!("day{day:02}"));
+        if !is_module_name(&module) {
+            return Err(eyre!(
+                "{module:?} is not a valid module name, expected a lowercase Rust identifier"
+            ));
+        }
+
+        Ok(Scaffold {
+            root: root.into(),
+            day,
+            module,
+        })
+    }
+
+    pub fn module_path(&self) -> PathBuf {
+        self.root.join("src").join(format!("{}.rs", self.module))
+    }
+
+    pub fn bin_path(&self) -> PathBuf {
+        self.root
+            .join("src")
+            .join("bin")
+            .join(format!("aoc{}.rs", self.day))
+    }
+
+    pub fn lib_path(&self) -> PathBuf {
+        self.root.join("src").join("lib.rs")
+    }
+
+    // Writes all the files, returning the paths that were created or modified. Nothing is written
+    // if any of the new files already exist
+    pub fn generate(&self) -> Result<Vec<PathBuf>> {
+        let module_path = self.module_path();
+        let bin_path = self.bin_path();
+        let lib_path = self.lib_path();
+
+        for path in [&module_path, &bin_path] {
+            if path.exists() {
+                return Err(eyre!("{path:?} already exists"));
+            }
+        }
+        let lib = add_module(&std::fs::read_to_string(&lib_path)?, &self.module)?;
+
+        std::fs::write(&module_path, self.render(MODULE_TEMPLATE))?;
+        std::fs::write(&bin_path, self.render(BIN_TEMPLATE))?;
+        std::fs::write(&lib_path, lib)?;
+
+        Ok(vec![module_path, bin_path, lib_path])
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{day}", &self.day.to_string())
+            .replace("{module}", &self.module)
+    }
+}
+
+fn is_module_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    (first.is_ascii_lowercase() || first == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && name != "_"
+        && !KEYWORDS.contains(&name)
+}
+
+// Inserts `pub mod <module>;` into the `pub mod` list of a `lib.rs`, keeping it sorted
+pub fn add_module(lib: &str, module: &str) -> Result<String> {
+    let new_line = format!("pub mod {module};");
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let mod_lines: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod "))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    if mod_lines.iter().any(|idx| lines[*idx] == new_line) {
+        return Err(eyre!("module {module} is already declared in lib.rs"));
+    }
+
+    let insert_at = mod_lines
+        .iter()
+        .find(|idx| lines[**idx] > new_line.as_str())
+        .copied()
+        .or_else(|| mod_lines.last().map(|idx| idx + 1))
+        .ok_or_else(|| eyre!("could not find the pub mod list in lib.rs"))?;
+    lines.insert(insert_at, &new_line);
+
+    Ok(lines.join("\n") + "\n")
+}
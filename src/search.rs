@@ -0,0 +1,220 @@
+use crate::{geometry::Vec2, grid::Grid};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+// Graph searches over any node type. Neighbours are expanded in the order the neighbour function
+// returns them, and nodes with the same priority are expanded in the order they were discovered,
+// so the same inputs always produce the same path
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    // Every node from the start to the goal, both included
+    pub nodes: Vec<N>,
+    pub cost: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<N> {
+    pub path: Option<Path<N>>,
+    // Every node that was expanded, in the order it was expanded
+    pub visited: Vec<N>,
+}
+
+// Finds the path with the fewest steps, counting each step as a cost of 1
+pub fn bfs<N, G, F, I>(start: N, mut is_goal: G, mut neighbours: F) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+    G: FnMut(&N) -> bool,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::new();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut visited = Vec::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        visited.push(node.clone());
+        if is_goal(&node) {
+            let nodes = rebuild_path(&parents, node);
+            let cost = nodes.len() as u64 - 1;
+            return SearchResult {
+                path: Some(Path { nodes, cost }),
+                visited,
+            };
+        }
+
+        for next in neighbours(&node) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    SearchResult {
+        path: None,
+        visited,
+    }
+}
+
+// Finds the cheapest path, where `cost` gives the cost of stepping from one node to the next
+pub fn dijkstra<N, G, F, I, C>(start: N, is_goal: G, neighbours: F, cost: C) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+    G: FnMut(&N) -> bool,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    C: FnMut(&N, &N) -> u64,
+{
+    astar(start, is_goal, neighbours, cost, |_| 0)
+}
+
+// Like `dijkstra`, but guided by `heuristic`, an estimate of the remaining cost to a goal. Nodes
+// are never expanded twice, so the path is only guaranteed to be the cheapest if the heuristic is
+// consistent: 0 at every goal, and never more than the cost of a step plus the heuristic of the
+// node it leads to. Never overestimating isn't enough. A path whose cost overflows `u64` is never
+// taken, and priorities (cost plus heuristic) saturate at `u64::MAX` rather than overflow
+pub fn astar<N, G, F, I, C, H>(
+    start: N,
+    mut is_goal: G,
+    mut neighbours: F,
+    mut cost: C,
+    mut heuristic: H,
+) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+    G: FnMut(&N) -> bool,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    C: FnMut(&N, &N) -> u64,
+    H: FnMut(&N) -> u64,
+{
+    let mut parents = HashMap::new();
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut done = HashSet::new();
+    let mut visited = Vec::new();
+
+    // The heap only holds indices into `discovered`. Pairing the priority with the discovery
+    // index gives us the deterministic tie-breaking
+    let mut discovered = vec![(start.clone(), 0)];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+
+    while let Some(Reverse((_, idx))) = heap.pop() {
+        let (node, node_cost) = discovered[idx].clone();
+        if !done.insert(node.clone()) {
+            continue;
+        }
+
+        visited.push(node.clone());
+        if is_goal(&node) {
+            return SearchResult {
+                path: Some(Path {
+                    nodes: rebuild_path(&parents, node),
+                    cost: node_cost,
+                }),
+                visited,
+            };
+        }
+
+        for next in neighbours(&node) {
+            if done.contains(&next) {
+                continue;
+            }
+            let Some(next_cost) = node_cost.checked_add(cost(&node, &next)) else {
+                continue;
+            };
+            if costs.get(&next).is_some_and(|known| *known <= next_cost) {
+                continue;
+            }
+
+            costs.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), node.clone());
+            let priority = next_cost.saturating_add(heuristic(&next));
+            heap.push(Reverse((priority, discovered.len())));
+            discovered.push((next, next_cost));
+        }
+    }
+
+    SearchResult {
+        path: None,
+        visited,
+    }
+}
+
+fn rebuild_path<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, goal: N) -> Vec<N> {
+    let mut nodes = vec![goal];
+    while let Some(parent) = parents.get(nodes.last().expect("never empty")) {
+        nodes.push(parent.clone());
+    }
+    nodes.reverse();
+    nodes
+}
+
+// Searches over the cells of a grid, moving between orthogonally adjacent cells
+impl<T> Grid<T> {
+    pub fn bfs<P: FnMut(&T) -> bool>(
+        &self,
+        start: Vec2,
+        goal: Vec2,
+        mut passable: P,
+    ) -> SearchResult<Vec2> {
+        bfs(
+            start,
+            |pos| *pos == goal,
+            |pos| self.passable_neighbours(*pos, &mut passable),
+        )
+    }
+
+    // `cost` is the cost of stepping into a cell
+    pub fn dijkstra<P, C>(
+        &self,
+        start: Vec2,
+        goal: Vec2,
+        mut passable: P,
+        mut cost: C,
+    ) -> SearchResult<Vec2>
+    where
+        P: FnMut(&T) -> bool,
+        C: FnMut(&T) -> u64,
+    {
+        dijkstra(
+            start,
+            |pos| *pos == goal,
+            |pos| self.passable_neighbours(*pos, &mut passable),
+            |_, next| cost(&self[*next]),
+        )
+    }
+
+    // Uses the Manhattan distance to the goal as the heuristic, so every step must cost at least 1
+    // for the path to be the cheapest
+    pub fn astar<P, C>(
+        &self,
+        start: Vec2,
+        goal: Vec2,
+        mut passable: P,
+        mut cost: C,
+    ) -> SearchResult<Vec2>
+    where
+        P: FnMut(&T) -> bool,
+        C: FnMut(&T) -> u64,
+    {
+        astar(
+            start,
+            |pos| *pos == goal,
+            |pos| self.passable_neighbours(*pos, &mut passable),
+            |_, next| cost(&self[*next]),
+            |pos| pos.manhattan(goal) as u64,
+        )
+    }
+
+    fn passable_neighbours<P: FnMut(&T) -> bool>(&self, pos: Vec2, passable: &mut P) -> Vec<Vec2> {
+        self.neighbours4(pos)
+            .filter(|(_, cell)| passable(cell))
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    geometry::{Bounds, Direction, Vec2},
+    grid::Grid,
+};
+use std::collections::HashMap;
+
+// A grid without fixed edges, for puzzles that grow without limit. Only occupied cells are stored,
+// positions can be negative, and the bounding box of the occupied cells is kept up to date
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Vec2, T>,
+    bounds: Option<Bounds<Vec2>>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    // The cells of `grid` for which `occupied` is true, with the top left corner of the grid moved
+    // to `origin`
+    pub fn from_grid<F: FnMut(&T) -> bool>(
+        grid: &Grid<T>,
+        origin: Vec2,
+        mut occupied: F,
+    ) -> SparseGrid<T>
+    where
+        T: Clone,
+    {
+        grid.iter()
+            .filter(|(_, cell)| occupied(cell))
+            .map(|(pos, cell)| (origin + pos, cell.clone()))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // The smallest box holding every occupied cell, or `None` if there are none
+    pub fn bounds(&self) -> Option<Bounds<Vec2>> {
+        self.bounds
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn get(&self, pos: Vec2) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    // Returns the previous value of the cell, if it was occupied
+    pub fn insert(&mut self, pos: Vec2, value: T) -> Option<T> {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(pos),
+            None => self.bounds = Some(Bounds::new(pos, pos)),
+        }
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Vec2) -> Option<T> {
+        let value = self.cells.remove(&pos)?;
+        // Only a cell on the edge of the box can make it shrink
+        if self.bounds.is_some_and(|bounds| {
+            pos.x == bounds.min.x
+                || pos.x == bounds.max.x
+                || pos.y == bounds.min.y
+                || pos.y == bounds.max.y
+        }) {
+            self.bounds = Bounds::from_points(self.cells.keys().copied());
+        }
+        Some(value)
+    }
+
+    // Every occupied cell along with its position, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+        self.cells.iter().map(|(pos, cell)| (*pos, cell))
+    }
+
+    // The up to 4 orthogonally adjacent cells that are occupied
+    pub fn neighbours4(&self, pos: Vec2) -> impl Iterator<Item = (Vec2, &T)> {
+        self.neighbours_in(pos, &Direction::CARDINAL)
+    }
+
+    // The up to 8 orthogonally and diagonally adjacent cells that are occupied
+    pub fn neighbours8(&self, pos: Vec2) -> impl Iterator<Item = (Vec2, &T)> {
+        self.neighbours_in(pos, &Direction::ALL)
+    }
+
+    // The cells one step away in each of `directions` that are occupied
+    pub fn neighbours_in<'a>(
+        &'a self,
+        pos: Vec2,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = (Vec2, &'a T)> {
+        directions.iter().filter_map(move |dir| {
+            let pos = pos + dir.offset();
+            self.get(pos).map(|cell| (pos, cell))
+        })
+    }
+
+    // A dense copy of the occupied window, with empty cells set to `fill`. Also returns the
+    // position the top left corner of the dense grid had in this one
+    pub fn to_grid(&self, fill: T) -> (Grid<T>, Vec2)
+    where
+        T: Clone,
+    {
+        let Some(bounds) = self.bounds else {
+            return (Grid::new(0, 0, fill), Vec2::ZERO);
+        };
+
+        let mut grid = Grid::new(bounds.width() as usize, bounds.height() as usize, fill);
+        for (pos, cell) in self.iter() {
+            grid[pos - bounds.min] = cell.clone();
+        }
+        (grid, bounds.min)
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T> FromIterator<(Vec2, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Vec2, T)>>(cells: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(cells);
+        grid
+    }
+}
+
+impl<T> Extend<(Vec2, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Vec2, T)>>(&mut self, cells: I) {
+        for (pos, cell) in cells {
+            self.insert(pos, cell);
+        }
+    }
+}
+
+impl<T> std::ops::Index<Vec2> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos} is not occupied"))
+    }
+}
+
+impl<T> std::ops::IndexMut<Vec2> for SparseGrid<T> {
+    fn index_mut(&mut self, pos: Vec2) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos} is not occupied"))
+    }
+}
+
+// Draws the occupied window only, with `.` for empty cells
+impl<T: std::fmt::Display> std::fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(bounds) = self.bounds else {
+            return Ok(());
+        };
+
+        for y in bounds.min.y..=bounds.max.y {
+            if y > bounds.min.y {
+                writeln!(f)?;
+            }
+            for x in bounds.min.x..=bounds.max.x {
+                match self.get(Vec2::new(x, y)) {
+                    Some(cell) => write!(f, "{cell}")?,
+                    None => write!(f, ".")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
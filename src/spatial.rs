@@ -0,0 +1,128 @@
+use crate::geometry::{Bounds, Vec2};
+use std::collections::BTreeMap;
+
+// A run of `len` cells along a row, starting at `start` and going right
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span<T> {
+    pub start: Vec2,
+    pub len: u64,
+    pub value: T,
+}
+
+// Horizontal spans indexed by row. The spans of a row never overlap and are kept sorted, so both
+// their starts and their ends are in order, and finding the spans touching a range of columns is
+// a binary search. A rectangle query costs O(log n) per row plus the number of spans it returns,
+// however long the spans are
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanIndex<T> {
+    rows: BTreeMap<i64, Vec<Span<T>>>,
+    len: usize,
+}
+
+impl<T> Span<T> {
+    // The column just past the last cell
+    pub fn end(&self) -> i64 {
+        self.start.x + self.len as i64
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        pos.y == self.start.y && pos.x >= self.start.x && pos.x < self.end()
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = Vec2> {
+        let start = self.start;
+        (0..self.len as i64).map(move |x| start + Vec2::new(x, 0))
+    }
+}
+
+impl<T> SpanIndex<T> {
+    pub fn new() -> SpanIndex<T> {
+        SpanIndex {
+            rows: BTreeMap::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Adds a span, removing and returning any spans it overlaps. Empty spans are ignored
+    pub fn insert(&mut self, start: Vec2, len: u64, value: T) -> Vec<Span<T>> {
+        if len == 0 {
+            return Vec::new();
+        }
+
+        let span = Span { start, len, value };
+        let row = self.rows.entry(start.y).or_default();
+        let (first, last) = Self::overlapping(row, start.x, span.end() - 1);
+        let removed: Vec<_> = row.splice(first..last, [span]).collect();
+        self.len = self.len + 1 - removed.len();
+        removed
+    }
+
+    // Removes the span covering `pos`, if any
+    pub fn remove(&mut self, pos: Vec2) -> Option<Span<T>> {
+        let row = self.rows.get_mut(&pos.y)?;
+        let (first, last) = Self::overlapping(row, pos.x, pos.x);
+        if first == last {
+            return None;
+        }
+
+        let span = row.remove(first);
+        if row.is_empty() {
+            self.rows.remove(&pos.y);
+        }
+        self.len -= 1;
+        Some(span)
+    }
+
+    // The span covering `pos`, if any
+    pub fn get(&self, pos: Vec2) -> Option<&Span<T>> {
+        let row = self.rows.get(&pos.y)?;
+        let (first, last) = Self::overlapping(row, pos.x, pos.x);
+        row[first..last].first()
+    }
+
+    // Every span with at least one cell inside `bounds`, row by row from left to right
+    pub fn query(&self, bounds: Bounds<Vec2>) -> impl Iterator<Item = &Span<T>> {
+        self.rows
+            .range(bounds.min.y..=bounds.max.y)
+            .flat_map(move |(_, row)| {
+                let (first, last) = Self::overlapping(row, bounds.min.x, bounds.max.x);
+                &row[first..last]
+            })
+    }
+
+    // Every span, row by row from left to right
+    pub fn iter(&self) -> impl Iterator<Item = &Span<T>> {
+        self.rows.values().flatten()
+    }
+
+    // The range of spans in `row` with a cell in the columns `min..=max`
+    fn overlapping(row: &[Span<T>], min: i64, max: i64) -> (usize, usize) {
+        let first = row.partition_point(|span| span.end() <= min);
+        let last = first + row[first..].partition_point(|span| span.start.x <= max);
+        (first, last)
+    }
+}
+
+impl<T> Default for SpanIndex<T> {
+    fn default() -> Self {
+        SpanIndex::new()
+    }
+}
+
+impl<T> FromIterator<Span<T>> for SpanIndex<T> {
+    fn from_iter<I: IntoIterator<Item = Span<T>>>(spans: I) -> Self {
+        let mut index = SpanIndex::new();
+        for span in spans {
+            index.insert(span.start, span.len, span.value);
+        }
+        index
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    geometry::{Direction, Vec2},
+    grid::Grid,
+};
+use clap::ValueEnum;
+
+// What lies past the edges of a grid, for neighbour queries
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Topology {
+    // Nothing, as in the original puzzles
+    #[default]
+    Bounded,
+    // The left and right edges are joined, like a cylinder
+    WrapX,
+    // Both pairs of opposite edges are joined
+    Torus,
+    // The grid is mirrored at its edges, so the cell past the edge is the one just before it
+    Reflect,
+}
+
+impl Topology {
+    // Where `pos` ends up on a `width` x `height` grid, or `None` if it isn't on the grid
+    pub fn resolve(&self, pos: Vec2, width: usize, height: usize) -> Option<Vec2> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let (w, h) = (width as i64, height as i64);
+        let pos = match self {
+            Topology::Bounded => pos,
+            Topology::WrapX => Vec2::new(pos.x.rem_euclid(w), pos.y),
+            Topology::Torus => Vec2::new(pos.x.rem_euclid(w), pos.y.rem_euclid(h)),
+            Topology::Reflect => Vec2::new(reflect(pos.x, w), reflect(pos.y, h)),
+        };
+        (pos.x >= 0 && pos.y >= 0 && pos.x < w && pos.y < h).then_some(pos)
+    }
+}
+
+// Bounces back and forth between 0 and `len - 1` without repeating the end cells
+fn reflect(v: i64, len: i64) -> i64 {
+    if len == 1 {
+        return 0;
+    }
+    let period = 2 * (len - 1);
+    let v = v.rem_euclid(period);
+    if v < len {
+        v
+    } else {
+        period - v
+    }
+}
+
+impl<T> Grid<T> {
+    // The cells one step away in each of `directions`, following the edges of `topology`. On
+    // small grids, or when reflecting, the same cell can be reached in more than one direction
+    pub fn neighbours_on<'a>(
+        &'a self,
+        pos: Vec2,
+        directions: &'static [Direction],
+        topology: Topology,
+    ) -> impl Iterator<Item = (Vec2, &'a T)> {
+        directions.iter().filter_map(move |dir| {
+            let pos = topology.resolve(pos + dir.offset(), self.width(), self.height())?;
+            self.get(pos).map(|cell| (pos, cell))
+        })
+    }
+}
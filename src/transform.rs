@@ -0,0 +1,308 @@
+use crate::{
+    geometry::{Bounds, Direction, Vec2},
+    grid::Grid,
+};
+
+// The eight ways of turning and mirroring a rectangle. Rotations are clockwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    // Mirrors left and right
+    FlipHorizontal,
+    // Mirrors top and bottom
+    FlipVertical,
+    // Mirrors along the diagonal from the top left corner, swapping rows and columns
+    Transpose,
+    // Mirrors along the diagonal from the top right corner
+    AntiTranspose,
+}
+
+// A transformed grid that reads through to the original cells instead of copying them
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    transform: Transform,
+}
+
+// A grid repeated forever in every direction, so every position has a cell
+#[derive(Debug)]
+pub struct Tiled<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::Transpose,
+        Transform::AntiTranspose,
+    ];
+
+    // Whether a `width` x `height` grid becomes `height` x `width`
+    pub fn swaps_axes(&self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90
+                | Transform::Rotate270
+                | Transform::Transpose
+                | Transform::AntiTranspose
+        )
+    }
+
+    // The position in a `width` x `height` grid that ends up at `pos` once transformed
+    pub fn source(&self, pos: Vec2, width: usize, height: usize) -> Vec2 {
+        let (w, h) = (width as i64, height as i64);
+        let Vec2 { x, y } = pos;
+        match self {
+            Transform::Identity => Vec2::new(x, y),
+            Transform::Rotate90 => Vec2::new(y, h - 1 - x),
+            Transform::Rotate180 => Vec2::new(w - 1 - x, h - 1 - y),
+            Transform::Rotate270 => Vec2::new(w - 1 - y, x),
+            Transform::FlipHorizontal => Vec2::new(w - 1 - x, y),
+            Transform::FlipVertical => Vec2::new(x, h - 1 - y),
+            Transform::Transpose => Vec2::new(y, x),
+            Transform::AntiTranspose => Vec2::new(w - 1 - y, h - 1 - x),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn view(&self, transform: Transform) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            transform,
+        }
+    }
+
+    pub fn tiled(&self) -> Tiled<'_, T> {
+        Tiled { grid: self }
+    }
+
+    pub fn transformed(&self, transform: Transform) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.view(transform).to_grid()
+    }
+
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::Transpose)
+    }
+
+    pub fn rotate_90(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::Rotate90)
+    }
+
+    pub fn rotate_180(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::Rotate180)
+    }
+
+    pub fn rotate_270(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::Rotate270)
+    }
+
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::FlipHorizontal)
+    }
+
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.transformed(Transform::FlipVertical)
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        if self.transform.swaps_axes() {
+            self.grid.height()
+        } else {
+            self.grid.width()
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        if self.transform.swaps_axes() {
+            self.grid.width()
+        } else {
+            self.grid.height()
+        }
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && pos.x < self.width() as i64 && pos.y < self.height() as i64
+    }
+
+    pub fn get(&self, pos: Vec2) -> Option<&'a T> {
+        if !self.contains(pos) {
+            return None;
+        }
+        self.grid.get(
+            self.transform
+                .source(pos, self.grid.width(), self.grid.height()),
+        )
+    }
+
+    // Iterates over every cell along with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &'a T)> {
+        let view = *self;
+        let (width, height) = (self.width() as i64, self.height() as i64);
+        (0..height)
+            .flat_map(move |y| (0..width).map(move |x| Vec2::new(x, y)))
+            .map(move |pos| (pos, view.cell(pos)))
+    }
+
+    pub fn row(&self, y: usize) -> Option<impl Iterator<Item = &'a T>> {
+        let view = *self;
+        if y < self.height() {
+            Some((0..self.width() as i64).map(move |x| view.cell(Vec2::new(x, y as i64))))
+        } else {
+            None
+        }
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let rows = (0..self.height())
+            .map(|y| self.row(y).expect("y is in the view").cloned().collect())
+            .collect();
+        Grid::from_rows(rows).expect("every row has the view's width")
+    }
+
+    fn cell(&self, pos: Vec2) -> &'a T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos} is outside of the view"))
+    }
+}
+
+// Written out rather than derived, which would require `T: Copy`
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<T> std::ops::Index<Vec2> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &Self::Output {
+        self.cell(pos)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for GridView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.row(y).expect("y is in the view") {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> Tiled<'a, T> {
+    // Only `None` if the grid has no cells
+    pub fn get(&self, pos: Vec2) -> Option<&'a T> {
+        self.grid.get(self.wrap(pos))
+    }
+
+    // The position in the original grid that `pos` is a copy of
+    pub fn wrap(&self, pos: Vec2) -> Vec2 {
+        let (w, h) = (
+            self.grid.width().max(1) as i64,
+            self.grid.height().max(1) as i64,
+        );
+        Vec2::new(pos.x.rem_euclid(w), pos.y.rem_euclid(h))
+    }
+
+    // Which copy of the grid `pos` falls in, with the original at [0, 0]
+    pub fn tile(&self, pos: Vec2) -> Vec2 {
+        let (w, h) = (
+            self.grid.width().max(1) as i64,
+            self.grid.height().max(1) as i64,
+        );
+        Vec2::new(pos.x.div_euclid(w), pos.y.div_euclid(h))
+    }
+
+    // The cells one step away in each of `directions`, which always exist
+    pub fn neighbours_in(
+        &self,
+        pos: Vec2,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = (Vec2, &'a T)> + '_ {
+        directions.iter().filter_map(move |dir| {
+            let pos = pos + dir.offset();
+            self.get(pos).map(|cell| (pos, cell))
+        })
+    }
+
+    pub fn neighbours4(&self, pos: Vec2) -> impl Iterator<Item = (Vec2, &'a T)> + '_ {
+        self.neighbours_in(pos, &Direction::CARDINAL)
+    }
+
+    pub fn neighbours8(&self, pos: Vec2) -> impl Iterator<Item = (Vec2, &'a T)> + '_ {
+        self.neighbours_in(pos, &Direction::ALL)
+    }
+
+    // A dense copy of the cells inside `bounds`
+    pub fn window(&self, bounds: Bounds<Vec2>) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let rows = (bounds.min.y..=bounds.max.y)
+            .map(|y| {
+                (bounds.min.x..=bounds.max.x)
+                    .map(|x| self[Vec2::new(x, y)].clone())
+                    .collect()
+            })
+            .collect();
+        Grid::from_rows(rows).expect("every row has the window's width")
+    }
+}
+
+impl<T> Clone for Tiled<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Tiled<'_, T> {}
+
+impl<T> std::ops::Index<Vec2> for Tiled<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &Self::Output {
+        self.get(pos).expect("a tiled grid must have cells")
+    }
+}
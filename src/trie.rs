@@ -1,6 +1,12 @@
 use std::{collections::HashMap, iter::Iterator};
 
-#[derive(Debug, Default, PartialEq, Eq)]
+// Serialized as the sorted list of its words, as the shape of the nodes follows from them
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "Vec<String>", from = "Vec<String>")
+)]
 pub struct Trie {
     root: TrieNode,
 }
@@ -18,6 +24,14 @@ impl Trie {
         self.root.add(String::new(), value);
     }
 
+    // Every value that was added, sorted
+    pub fn words(&self) -> Vec<String> {
+        let mut words = Vec::new();
+        self.root.collect_words(&mut words);
+        words.sort();
+        words
+    }
+
     pub fn get_searcher<'a>(&'a self) -> TrieSearcher<'a> {
         TrieSearcher {
             trie_node: &self.root,
@@ -26,13 +40,25 @@ impl Trie {
     }
 }
 
+impl From<Trie> for Vec<String> {
+    fn from(trie: Trie) -> Self {
+        trie.words()
+    }
+}
+
+impl From<Vec<String>> for Trie {
+    fn from(words: Vec<String>) -> Self {
+        Trie::new(words.iter().map(|word| word.as_str()))
+    }
+}
+
 impl std::fmt::Display for Trie {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.root)
     }
 }
 
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 struct TrieNode {
     val: String,
     full: bool,
@@ -67,6 +93,15 @@ impl TrieNode {
     fn is_full(&self) -> bool {
         self.full
     }
+
+    fn collect_words(&self, words: &mut Vec<String>) {
+        if self.full {
+            words.push(self.val.clone());
+        }
+        for child in self.children.values() {
+            child.collect_words(words);
+        }
+    }
 }
 
 impl std::fmt::Display for TrieNode {
@@ -118,4 +153,8 @@ impl<'a> TrieSearcher<'a> {
     pub fn len(&self) -> usize {
         self.processed_chars
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.processed_chars == 0
+    }
 }
@@ -0,0 +1,130 @@
+use aoc_2023::{
+    cache::{Answers, InputCache},
+    Opt,
+};
+use clap::Parser;
+use std::path::PathBuf;
+
+// A cache in a directory of its own, which is removed when the test is done with it
+struct TempCache {
+    dir: PathBuf,
+    cache: InputCache,
+}
+
+impl TempCache {
+    fn new(name: &str) -> TempCache {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = InputCache::new(Some(dir.join("inputs")));
+        TempCache { dir, cache }
+    }
+
+    // A file outside of the cache to add from
+    fn source(&self, contents: &str) -> PathBuf {
+        std::fs::create_dir_all(&self.dir).unwrap();
+        let path = self.dir.join("source.txt");
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+}
+
+impl Drop for TempCache {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[test]
+fn add_input_and_example() {
+    let temp = TempCache::new("add");
+    let cache = &temp.cache;
+    assert!(cache.expected_answers(3).unwrap().is_none());
+
+    let path = cache.add_input(3, &temp.source("input")).unwrap();
+    assert_eq!(path, cache.input_path(3));
+    assert_eq!(
+        std::fs::read_to_string(cache.path(3, false)).unwrap(),
+        "input"
+    );
+
+    let answers = Answers::from([(1, 4361), (2, 467835)]);
+    let path = cache
+        .add_example(3, &temp.source("example"), &answers)
+        .unwrap();
+    assert_eq!(path, cache.example_path(3));
+    assert_eq!(
+        std::fs::read_to_string(cache.path(3, true)).unwrap(),
+        "example"
+    );
+    assert_eq!(
+        std::fs::read_to_string(cache.answers_path(3)).unwrap(),
+        "part1: 4361\npart2: 467835\n"
+    );
+    assert_eq!(cache.expected_answers(3).unwrap(), Some(answers));
+
+    let listed = cache.list();
+    assert_eq!(listed.len(), 25);
+    assert!(listed[2].input && listed[2].example && listed[2].answers);
+    assert!(!listed[0].input && !listed[0].example && !listed[0].answers);
+}
+
+#[test]
+fn example_without_answers_removes_old_answers() {
+    let temp = TempCache::new("stale");
+    let cache = &temp.cache;
+    cache
+        .add_example(1, &temp.source("old"), &Answers::from([(1, 142)]))
+        .unwrap();
+    assert!(cache.expected_answers(1).unwrap().is_some());
+
+    cache
+        .add_example(1, &temp.source("new"), &Answers::new())
+        .unwrap();
+    assert!(!cache.answers_path(1).exists());
+    assert_eq!(cache.expected_answers(1).unwrap(), None);
+}
+
+#[test]
+fn answers_file_format() {
+    let temp = TempCache::new("format");
+    let cache = &temp.cache;
+    std::fs::create_dir_all(cache.dir()).unwrap();
+
+    std::fs::write(cache.answers_path(2), "\n part2 :  2286 \n\npart1: 8\n").unwrap();
+    assert_eq!(
+        cache.expected_answers(2).unwrap(),
+        Some(Answers::from([(1, 8), (2, 2286)]))
+    );
+
+    for bad in ["part1 8", "1: 8", "partone: 8", "part1: eight", "part1: -8"] {
+        std::fs::write(cache.answers_path(2), bad).unwrap();
+        assert!(cache.expected_answers(2).is_err(), "{bad:?} was accepted");
+    }
+}
+
+#[test]
+fn missing_cached_input_names_the_path() {
+    let temp = TempCache::new("missing");
+    let dir = temp.cache.dir().to_str().unwrap();
+    for (example, file, flag) in [
+        (false, "day09.txt", ""),
+        (true, "day09.example.txt", " --example"),
+    ] {
+        let mut args = vec!["aoc", "--day", "9", "--input-dir", dir];
+        if example {
+            args.push("--example");
+        }
+        let opt = Opt::parse_from(args);
+        let err = aoc_2023::read_lines(&opt).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        let message = err.to_string();
+        assert!(
+            message.contains(&temp.cache.dir().join(file).display().to_string()),
+            "{message}"
+        );
+        assert!(
+            message.contains(&format!("aoc inputs add --day 9{flag} <file>")),
+            "{message}"
+        );
+    }
+}
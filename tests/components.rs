@@ -0,0 +1,129 @@
+use aoc_2023::{
+    components::Connectivity,
+    engine::Schematic,
+    geometry::{Bounds, Vec2},
+    grid::Grid,
+};
+
+fn grid(lines: &[&str]) -> Grid<char> {
+    Grid::parse(lines.iter().copied(), '.')
+}
+
+fn bounds(min: (i64, i64), max: (i64, i64)) -> Bounds<Vec2> {
+    Bounds::new(Vec2::new(min.0, min.1), Vec2::new(max.0, max.1))
+}
+
+fn is_hash(c: &char) -> bool {
+    *c == '#'
+}
+
+// Two blocks that only touch diagonally, and a single cell on its own
+const DIAGONAL: [&str; 4] = ["##...", "##...", "..#.#", "...#."];
+
+#[test]
+fn flood_fill_follows_the_connectivity() {
+    let grid = grid(&DIAGONAL);
+
+    let four = grid
+        .flood_fill(Vec2::new(0, 0), Connectivity::Four, is_hash)
+        .unwrap();
+    assert_eq!(four.size(), 4);
+    assert_eq!(four.bounds, bounds((0, 0), (1, 1)));
+    // Breadth first, neighbours in the order north, east, south, west
+    assert_eq!(
+        four.cells,
+        vec![
+            Vec2::new(0, 0),
+            Vec2::new(1, 0),
+            Vec2::new(0, 1),
+            Vec2::new(1, 1)
+        ]
+    );
+
+    let eight = grid
+        .flood_fill(Vec2::new(0, 0), Connectivity::Eight, is_hash)
+        .unwrap();
+    assert_eq!(eight.size(), 7);
+    assert_eq!(eight.bounds, bounds((0, 0), (4, 3)));
+}
+
+#[test]
+fn flood_fill_from_a_cell_that_does_not_match() {
+    let grid = grid(&DIAGONAL);
+    assert_eq!(
+        grid.flood_fill(Vec2::new(4, 0), Connectivity::Eight, is_hash),
+        None
+    );
+    assert_eq!(
+        grid.flood_fill(Vec2::new(9, 9), Connectivity::Eight, is_hash),
+        None
+    );
+}
+
+#[test]
+fn components_with_four_and_eight_connectivity() {
+    let grid = grid(&DIAGONAL);
+
+    let four = grid.components(Connectivity::Four, is_hash);
+    let sizes: Vec<_> = four.components.iter().map(|c| c.size()).collect();
+    assert_eq!(sizes, vec![4, 1, 1, 1]);
+    let all_bounds: Vec<_> = four.components.iter().map(|c| c.bounds).collect();
+    assert_eq!(
+        all_bounds,
+        vec![
+            bounds((0, 0), (1, 1)),
+            bounds((2, 2), (2, 2)),
+            bounds((4, 2), (4, 2)),
+            bounds((3, 3), (3, 3)),
+        ]
+    );
+    assert_eq!(four.labels[Vec2::new(1, 1)], Some(0));
+    assert_eq!(four.labels[Vec2::new(3, 3)], Some(3));
+    assert_eq!(four.labels[Vec2::new(2, 0)], None);
+
+    let eight = grid.components(Connectivity::Eight, is_hash);
+    assert_eq!(eight.components.len(), 1);
+    assert_eq!(eight.components[0].size(), 7);
+    assert_eq!(
+        eight.component_at(Vec2::new(4, 2)),
+        Some(&eight.components[0])
+    );
+    assert_eq!(eight.component_at(Vec2::new(4, 3)), None);
+    assert_eq!(eight.component_at(Vec2::new(-1, 0)), None);
+}
+
+#[test]
+fn components_of_an_empty_grid() {
+    let labels = grid(&[]).components(Connectivity::Eight, is_hash);
+    assert!(labels.components.is_empty());
+}
+
+#[test]
+fn machine_parts_group_numbers_with_their_symbols() {
+    let schematic = Schematic::parse(
+        [
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+        ]
+        .into_iter(),
+    )
+    .unwrap();
+
+    let parts = schematic.machine_parts();
+    let found: Vec<_> = parts
+        .iter()
+        .map(|part| (part.size(), part.bounds))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (6, bounds((0, 0), (3, 2))),
+            (3, bounds((5, 0), (7, 0))),
+            (4, bounds((6, 2), (8, 3))),
+            (4, bounds((0, 4), (3, 4))),
+        ]
+    );
+}
@@ -0,0 +1,486 @@
+use aoc_2023::engine::{
+    Aggregation, GearRule, ParseError, ParseWarning, PartId, Schematic, SchematicConfig, Vec2,
+};
+
+#[test]
+fn numbers_with_ten_or_more_digits() {
+    let schematic =
+        Schematic::parse(["12345678901*...", "..............$", ".987654321987654"].into_iter())
+            .unwrap();
+    let mut parts = schematic.get_valid_part_numbers();
+    parts.sort();
+    assert_eq!(parts, vec![12345678901, 987654321987654]);
+
+    let max = Schematic::parse(["18446744073709551615#"].into_iter()).unwrap();
+    assert_eq!(max.get_valid_part_numbers(), vec![u64::MAX]);
+}
+
+#[test]
+fn gear_ratios_that_overflow_have_no_value() {
+    let schematic = Schematic::parse(["12345678901*12345678901"].into_iter()).unwrap();
+    let gears = schematic.get_gears();
+    assert_eq!(gears.len(), 1);
+    assert_eq!(gears[0].value, None);
+
+    let schematic = Schematic::parse(["4294967296*4294967295"].into_iter()).unwrap();
+    assert_eq!(schematic.get_gears()[0].value, Some(18446744069414584320));
+
+    let max = Schematic::parse(["18446744073709551615", "+1.."].into_iter()).unwrap();
+    let sum = GearRule::default()
+        .symbols(['+'])
+        .aggregation(Aggregation::Sum);
+    assert_eq!(max.find_gears(&sum)[0].value, None);
+    let rule = GearRule::default().symbols(['+']).exactly(2);
+    assert_eq!(max.find_gears(&rule)[0].value, Some(u64::MAX));
+}
+
+#[test]
+fn numbers_that_overflow_are_an_error() {
+    let err = Schematic::parse(["......", "..18446744073709551616*"].into_iter()).unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::Overflow {
+            pos: Vec2::new(2, 1)
+        }
+    );
+}
+
+#[test]
+fn zero_part_number() {
+    let schematic = Schematic::parse(["0*5", "...", "0.."].into_iter()).unwrap();
+    let mut parts = schematic.get_valid_part_numbers();
+    parts.sort();
+    assert_eq!(parts, vec![0, 5]);
+
+    let gears = schematic.get_gears();
+    assert_eq!(gears.len(), 1);
+    assert_eq!(gears[0].value, Some(0));
+
+    let orphans: Vec<_> = schematic.graph().orphan_parts().collect();
+    assert_eq!(orphans.len(), 1);
+    assert_eq!(
+        orphans[0].id,
+        PartId {
+            start: Vec2::new(0, 2),
+            len: 1
+        }
+    );
+}
+
+#[test]
+fn leading_zeroes_are_part_of_the_span() {
+    // The symbol only touches the last zero-padded digit, so the span must cover all three
+    let schematic = Schematic::parse([".007.", "....#", "007.."].into_iter()).unwrap();
+    let parts: Vec<_> = schematic.graph().valid_parts().collect();
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].value, 7);
+    assert_eq!(
+        parts[0].id,
+        PartId {
+            start: Vec2::new(1, 0),
+            len: 3
+        }
+    );
+
+    // The symbol only touches the first zero. Measuring the span from the value would place 7 at
+    // [2, 0] alone, two cells away from it
+    let schematic = Schematic::parse(["007.", "*..."].into_iter()).unwrap();
+    assert_eq!(schematic.get_valid_part_numbers(), vec![7]);
+}
+
+#[test]
+fn number_at_the_end_of_a_line_after_unicode() {
+    let schematic = Schematic::parse(["×.12", "...#"].into_iter()).unwrap();
+    let parts: Vec<_> = schematic.graph().valid_parts().collect();
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].id.start, Vec2::new(2, 0));
+}
+
+const EXAMPLE: [&str; 10] = [
+    "467..114..",
+    "...*......",
+    "..35..633.",
+    "......#...",
+    "617*......",
+    ".....+.58.",
+    "..592.....",
+    "......755.",
+    "...$.*....",
+    ".664.598..",
+];
+
+#[test]
+fn edits_match_parsing_the_edited_text() {
+    let mut lines: Vec<String> = EXAMPLE.iter().map(|line| line.to_string()).collect();
+    let mut schematic = Schematic::parse(EXAMPLE.into_iter()).unwrap();
+
+    // Joining, splitting, growing and shrinking numbers, and adding and removing symbols
+    let edits = [
+        (Vec2::new(3, 0), '9'),
+        (Vec2::new(4, 0), '1'),
+        (Vec2::new(1, 0), '.'),
+        (Vec2::new(3, 1), '.'),
+        (Vec2::new(2, 5), '#'),
+        (Vec2::new(5, 4), '%'),
+        (Vec2::new(6, 3), '7'),
+        (Vec2::new(3, 6), 'x'),
+        (Vec2::new(3, 6), '+'),
+        (Vec2::new(9, 9), '2'),
+    ];
+    for (pos, c) in edits {
+        schematic.set(pos, c).unwrap();
+        let line = &mut lines[pos.y as usize];
+        line.replace_range(pos.x as usize..pos.x as usize + 1, &c.to_string());
+
+        let parsed = Schematic::parse(lines.iter().map(|line| line.as_str())).unwrap();
+        assert_eq!(schematic, parsed, "after setting {pos} to {c}");
+    }
+
+    schematic.clear(Vec2::new(2, 5)).unwrap();
+    assert_eq!(schematic.grid()[Vec2::new(2, 5)], '.');
+}
+
+#[test]
+fn failed_edits_change_nothing() {
+    let mut schematic = Schematic::parse(["9999999999.9999999999*"].into_iter()).unwrap();
+    let before = schematic.clone();
+
+    assert_eq!(
+        schematic.set(Vec2::new(10, 0), '9'),
+        Err(ParseError::Overflow {
+            pos: Vec2::new(0, 0)
+        })
+    );
+    assert_eq!(
+        schematic.set(Vec2::new(0, 1), '*'),
+        Err(ParseError::OutOfBounds {
+            pos: Vec2::new(0, 1)
+        })
+    );
+    assert_eq!(schematic, before);
+}
+
+#[test]
+fn diff_reports_parts_validity_and_gears() {
+    let before = Schematic::parse(EXAMPLE.into_iter()).unwrap();
+    let mut after = before.clone();
+    for (pos, c) in [
+        (Vec2::new(3, 1), '.'),
+        (Vec2::new(3, 0), '8'),
+        (Vec2::new(2, 2), '.'),
+        (Vec2::new(3, 2), '.'),
+        (Vec2::new(7, 5), '.'),
+        (Vec2::new(9, 9), '1'),
+    ] {
+        after.set(pos, c).unwrap();
+    }
+
+    let diff = before.diff(&after);
+    let values =
+        |parts: &[aoc_2023::engine::Part]| parts.iter().map(|p| p.value).collect::<Vec<_>>();
+    assert_eq!(diff.cells.len(), 6);
+    assert_eq!(values(&diff.parts_removed), vec![35]);
+    assert_eq!(values(&diff.parts_added), vec![1]);
+    assert_eq!(
+        diff.parts_altered
+            .iter()
+            .map(|change| (change.before.value, change.after.value))
+            .collect::<Vec<_>>(),
+        vec![(467, 4678), (58, 8)]
+    );
+    // 467 lost its only symbol. 58 never had one
+    assert_eq!(
+        diff.validity
+            .iter()
+            .map(|change| (change.after.value, change.valid))
+            .collect::<Vec<_>>(),
+        vec![(4678, false)]
+    );
+    assert_eq!(
+        diff.gears_lost.iter().map(|g| g.pos).collect::<Vec<_>>(),
+        vec![Vec2::new(3, 1)]
+    );
+    assert!(diff.gears_gained.is_empty());
+    assert!(diff
+        .to_string()
+        .lines()
+        .any(|line| line == "- gear * at [3, 1]: parts 467, 35 = 16345"));
+    assert!(before.diff(&before).is_empty());
+}
+
+fn sorted_parts(schematic: &Schematic) -> Vec<u64> {
+    let mut parts = schematic.get_valid_part_numbers();
+    parts.sort();
+    parts
+}
+
+fn symbols(schematic: &Schematic) -> Vec<(Vec2, char)> {
+    let mut symbols: Vec<_> = schematic
+        .graph()
+        .symbols()
+        .map(|(pos, symbol)| (pos, symbol.0))
+        .collect();
+    symbols.sort_by_key(|(pos, _)| (pos.y, pos.x));
+    symbols
+}
+
+#[test]
+fn parse_with_the_default_config_matches_parse() {
+    let parsed = Schematic::parse(EXAMPLE.into_iter()).unwrap();
+    let parsed_with =
+        Schematic::parse_with(EXAMPLE.into_iter(), &SchematicConfig::default()).unwrap();
+    assert_eq!(parsed, parsed_with);
+    assert_eq!(
+        sorted_parts(&parsed),
+        vec![35, 467, 592, 598, 617, 633, 664, 755]
+    );
+    assert!(parsed.warnings().is_empty());
+
+    // Owned lines work too
+    let owned = EXAMPLE.iter().map(|line| line.to_string());
+    assert_eq!(Schematic::parse(owned).unwrap(), parsed);
+}
+
+#[test]
+fn symbol_set() {
+    let config = SchematicConfig::with_symbols(['#']);
+    let schematic = Schematic::parse_with(["1#.2*.3"].into_iter(), &config).unwrap();
+    assert_eq!(sorted_parts(&schematic), vec![1]);
+    assert_eq!(symbols(&schematic), vec![(Vec2::new(1, 0), '#')]);
+    assert_eq!(
+        schematic.warnings(),
+        &[ParseWarning {
+            pos: Vec2::new(4, 0),
+            c: '*'
+        }]
+    );
+}
+
+#[test]
+fn any_symbol_except_the_background() {
+    let config = SchematicConfig::any_symbol('.');
+    let schematic = Schematic::parse_with(["1a.2×.3 ", "......."].into_iter(), &config).unwrap();
+    assert_eq!(sorted_parts(&schematic), vec![1, 2, 3]);
+    assert_eq!(
+        symbols(&schematic),
+        vec![
+            (Vec2::new(1, 0), 'a'),
+            (Vec2::new(4, 0), '×'),
+            (Vec2::new(7, 0), ' ')
+        ]
+    );
+    assert!(schematic.warnings().is_empty());
+}
+
+#[test]
+fn custom_classifier() {
+    let config = SchematicConfig::with_classifier(|c| c.is_ascii_uppercase());
+    let schematic = Schematic::parse_with(["1A.2b.3", "......C"].into_iter(), &config).unwrap();
+    assert_eq!(sorted_parts(&schematic), vec![1, 3]);
+    assert_eq!(
+        symbols(&schematic),
+        vec![(Vec2::new(1, 0), 'A'), (Vec2::new(6, 1), 'C')]
+    );
+    assert_eq!(
+        schematic.warnings(),
+        &[ParseWarning {
+            pos: Vec2::new(4, 0),
+            c: 'b'
+        }]
+    );
+
+    // Classifiers are only equal to themselves
+    let other = SchematicConfig::with_classifier(|c| c.is_ascii_uppercase());
+    assert_eq!(config, config.clone());
+    assert_ne!(config, other);
+}
+
+#[test]
+fn background_other_than_dot() {
+    let example = EXAMPLE.map(|line| line.replace('.', " "));
+    let config = SchematicConfig {
+        background: ' ',
+        ..Default::default()
+    };
+    let schematic =
+        Schematic::parse_with(example.iter().map(|line| line.as_str()), &config).unwrap();
+    assert_eq!(
+        sorted_parts(&schematic),
+        vec![35, 467, 592, 598, 617, 633, 664, 755]
+    );
+    assert!(schematic.warnings().is_empty());
+
+    // `.` isn't in the default set, so once it's not the background it's unclassified
+    let schematic = Schematic::parse_with(["12 ", "  ."].into_iter(), &config).unwrap();
+    assert!(sorted_parts(&schematic).is_empty());
+    assert_eq!(
+        schematic.warnings(),
+        &[ParseWarning {
+            pos: Vec2::new(2, 1),
+            c: '.'
+        }]
+    );
+
+    // With any symbol allowed, only the background is left out
+    let config = SchematicConfig::any_symbol('_');
+    let schematic = Schematic::parse_with(["4_5.", "____"].into_iter(), &config).unwrap();
+    assert_eq!(sorted_parts(&schematic), vec![5]);
+}
+
+#[test]
+fn unclassified_characters_are_warnings() {
+    let schematic = Schematic::parse(["1×.2", "..é.", "3..×"].into_iter()).unwrap();
+    assert_eq!(sorted_parts(&schematic), Vec::<u64>::new());
+    assert!(symbols(&schematic).is_empty());
+
+    let warnings: Vec<_> = schematic
+        .warnings()
+        .iter()
+        .map(|warning| warning.to_string())
+        .collect();
+    assert_eq!(
+        warnings,
+        vec![
+            "unclassified character '×' at [1, 0]",
+            "unclassified character 'é' at [2, 1]",
+            "unclassified character '×' at [3, 2]"
+        ]
+    );
+    // They are kept in the grid as they are
+    assert_eq!(schematic.grid()[Vec2::new(1, 0)], '×');
+}
+
+// A `*` touching three parts, a `#` touching two and a `*` touching one
+const GEARS: [&str; 4] = ["2.3..5#", ".*....7", "4......", "....*9."];
+
+fn gears(rule: &GearRule) -> Vec<(Vec2, char, Vec<u64>, u64)> {
+    let schematic = Schematic::parse(GEARS.into_iter()).unwrap();
+    let mut gears: Vec<_> = schematic
+        .find_gears(rule)
+        .into_iter()
+        .map(|gear| {
+            let values = gear.parts.iter().map(|part| part.value).collect();
+            (gear.pos, gear.symbol.0, values, gear.value.unwrap())
+        })
+        .collect();
+    gears.sort_by_key(|(pos, ..)| (pos.y, pos.x));
+    gears
+}
+
+#[test]
+fn default_rule_is_a_star_with_two_parts() {
+    assert!(gears(&GearRule::default()).is_empty());
+    assert_eq!(
+        gears(&GearRule::default().any_symbol()),
+        vec![(Vec2::new(6, 0), '#', vec![5, 7], 35)]
+    );
+
+    let example = Schematic::parse(EXAMPLE.into_iter()).unwrap();
+    let values: Vec<_> = example
+        .get_gears()
+        .iter()
+        .map(|gear| gear.value.unwrap())
+        .collect();
+    assert_eq!(values.iter().sum::<u64>(), 467835);
+}
+
+#[test]
+fn rules_with_other_part_counts() {
+    assert_eq!(
+        gears(&GearRule::default().exactly(3)),
+        vec![(Vec2::new(1, 1), '*', vec![2, 3, 4], 24)]
+    );
+    assert_eq!(
+        gears(&GearRule::default().exactly(1)),
+        vec![(Vec2::new(4, 3), '*', vec![9], 9)]
+    );
+    assert_eq!(
+        gears(&GearRule::default().parts(1..=3)),
+        vec![
+            (Vec2::new(1, 1), '*', vec![2, 3, 4], 24),
+            (Vec2::new(4, 3), '*', vec![9], 9)
+        ]
+    );
+    assert_eq!(
+        gears(&GearRule::default().any_symbol().parts(2..=3))
+            .iter()
+            .map(|(pos, ..)| *pos)
+            .collect::<Vec<_>>(),
+        vec![Vec2::new(6, 0), Vec2::new(1, 1)]
+    );
+    assert!(gears(&GearRule::default().parts(4..=10)).is_empty());
+}
+
+#[test]
+fn rules_with_a_symbol_set() {
+    assert_eq!(
+        gears(&GearRule::default().symbols(['#'])),
+        vec![(Vec2::new(6, 0), '#', vec![5, 7], 35)]
+    );
+    assert!(GearRule::default().symbols(['#', '$']).matches_symbol('$'));
+    assert!(!GearRule::default().symbols(['#', '$']).matches_symbol('*'));
+    assert!(GearRule::default().any_symbol().matches_symbol('×'));
+}
+
+#[test]
+fn gears_display_their_parts_and_value() {
+    let schematic = Schematic::parse(GEARS.into_iter()).unwrap();
+    let describe = |rule: &GearRule| {
+        let mut gears = schematic.find_gears(rule);
+        gears.sort_by_key(|gear| (gear.pos.y, gear.pos.x));
+        gears
+            .iter()
+            .map(|gear| gear.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // The same for every aggregation, as the gear doesn't know which one was used
+    assert_eq!(
+        describe(&GearRule::default().exactly(3)),
+        vec!["* at [1, 1]: parts 2, 3, 4 = 24"]
+    );
+    assert_eq!(
+        describe(&GearRule::default().exactly(3).aggregation(Aggregation::Sum)),
+        vec!["* at [1, 1]: parts 2, 3, 4 = 9"]
+    );
+
+    let overflowing = Schematic::parse(["18446744073709551615*2"].into_iter()).unwrap();
+    assert_eq!(
+        overflowing.get_gears()[0].to_string(),
+        "* at [20, 0]: parts 18446744073709551615, 2 = overflow"
+    );
+}
+
+#[test]
+fn aggregations() {
+    let rule = GearRule::default().parts(1..=3);
+    let sums = gears(&rule.clone().aggregation(Aggregation::Sum));
+    assert_eq!(
+        sums.iter().map(|(.., value)| *value).collect::<Vec<_>>(),
+        vec![9, 9]
+    );
+
+    let largest = Aggregation::custom(|parts| parts.iter().map(|part| part.value).max());
+    let maxes = gears(&rule.aggregation(largest));
+    assert_eq!(
+        maxes.iter().map(|(.., value)| *value).collect::<Vec<_>>(),
+        vec![4, 9]
+    );
+
+    assert_eq!(Aggregation::Product.apply(&[]), Some(1));
+    assert_eq!(Aggregation::Sum.apply(&[]), Some(0));
+
+    // A custom aggregation reports its own overflow, which is passed through as is
+    let doubled = Aggregation::custom(|parts| {
+        parts.iter().try_fold(0u64, |acc, part| {
+            acc.checked_add(part.value.checked_mul(2)?)
+        })
+    });
+    let schematic = Schematic::parse(["18446744073709551615*1"].into_iter()).unwrap();
+    let values: Vec<_> = schematic
+        .find_gears(&GearRule::default().aggregation(doubled))
+        .iter()
+        .map(|gear| gear.value)
+        .collect();
+    assert_eq!(values, vec![None]);
+}
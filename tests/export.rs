@@ -0,0 +1,85 @@
+use aoc_2023::{
+    engine::Schematic,
+    export::{CellClass, Colour, Picture},
+    geometry::Vec2,
+    grid::Grid,
+};
+
+// The attributes of every glyph at a cell size of 10
+const TEXT: &str = concat!(
+    r#"font-family="monospace" font-size="7" text-anchor="middle" "#,
+    r##"dominant-baseline="central" fill="#000000""##
+);
+
+#[test]
+fn svg_of_a_small_schematic() {
+    let schematic = Schematic::parse(["1*2", "..&"].into_iter()).unwrap();
+    let svg = Picture::from_schematic(&schematic).cell_size(10).to_svg();
+
+    // Parts are green, the gear yellow, other symbols blue and empty cells white. Only cells that
+    // aren't empty get a glyph
+    let expected = [
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="20" viewBox="0 0 30 20">"#
+            .to_string(),
+        r##"<rect x="0" y="0" width="10" height="10" fill="#98c379" stroke="#dddddd"/>"##.into(),
+        r##"<rect x="10" y="0" width="10" height="10" fill="#e5c07b" stroke="#dddddd"/>"##.into(),
+        r##"<rect x="20" y="0" width="10" height="10" fill="#98c379" stroke="#dddddd"/>"##.into(),
+        r##"<rect x="0" y="10" width="10" height="10" fill="#ffffff" stroke="#dddddd"/>"##.into(),
+        r##"<rect x="10" y="10" width="10" height="10" fill="#ffffff" stroke="#dddddd"/>"##.into(),
+        r##"<rect x="20" y="10" width="10" height="10" fill="#61afef" stroke="#dddddd"/>"##.into(),
+        format!(r#"<text x="5" y="5" {TEXT}>1</text>"#),
+        format!(r#"<text x="15" y="5" {TEXT}>*</text>"#),
+        format!(r#"<text x="25" y="5" {TEXT}>2</text>"#),
+        format!(r#"<text x="25" y="15" {TEXT}>&amp;</text>"#),
+        "</svg>".into(),
+        "".into(),
+    ];
+    assert_eq!(svg, expected.join("\n"));
+    assert_eq!(
+        Picture::from_schematic(&schematic).cell_size(10).to_svg(),
+        svg
+    );
+}
+
+#[test]
+fn svg_overlays() {
+    let grid = Grid::new(2, 2, CellClass::Empty);
+    let component = grid
+        .components(aoc_2023::components::Connectivity::Four, |_| true)
+        .components
+        .remove(0);
+    let svg = Picture::new(grid)
+        .cell_size(4)
+        .overlay_path(&[Vec2::new(0, 0), Vec2::new(1, 1)], Colour::PATH)
+        .overlay_component(&component, Colour::AREA)
+        .to_svg();
+
+    assert!(svg.contains(
+        r##"<polyline points="2,2 6,6" fill="none" stroke="#c678dd" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"/>"##
+    ));
+    assert!(svg.contains(
+        r##"<rect x="0" y="0" width="8" height="8" fill="none" stroke="#56b6c2" stroke-width="2"/>"##
+    ));
+    assert!(!svg.contains("<text"));
+}
+
+#[test]
+fn png_of_a_small_schematic() {
+    let schematic = Schematic::parse(["1*2"].into_iter()).unwrap();
+    let picture = Picture::from_schematic(&schematic).cell_size(4);
+    assert_eq!((picture.width(), picture.height()), (12, 4));
+    let png = picture.to_png().unwrap();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    assert_eq!(picture.to_png().unwrap(), png);
+}
+
+#[test]
+fn empty_pictures() {
+    let schematic = Schematic::parse(std::iter::empty::<&str>()).unwrap();
+    let picture = Picture::from_schematic(&schematic);
+    assert!(picture.is_empty());
+    assert!(picture.to_svg().contains(r#"width="0" height="0""#));
+    assert!(picture.to_png().is_err());
+
+    assert!(!Picture::new(Grid::new(1, 1, CellClass::Empty)).is_empty());
+}
@@ -0,0 +1,111 @@
+use aoc_2023::{
+    engine::{Schematic, SchematicConfig},
+    generator::Generator,
+};
+
+fn check(generator: &Generator) {
+    let generated = generator.generate();
+    let schematic = Schematic::parse(generated.lines()).unwrap();
+    let part_sum = schematic
+        .get_valid_part_numbers()
+        .iter()
+        .try_fold(0u64, |sum, value| sum.checked_add(*value));
+    let gear_ratio_sum = schematic
+        .get_gears()
+        .iter()
+        .try_fold(0u64, |sum, gear| sum.checked_add(gear.value?));
+
+    assert_eq!(part_sum, generated.part_sum, "{generator:?}");
+    assert_eq!(gear_ratio_sum, generated.gear_ratio_sum, "{generator:?}");
+}
+
+#[test]
+fn answers_match_the_schematic() {
+    for seed in 0..50 {
+        check(&Generator::new(40, 30).seed(seed));
+    }
+}
+
+#[test]
+fn dense_and_sparse_inputs() {
+    for seed in 0..20 {
+        for (digits, symbols) in [(0.9, 0.5), (0.05, 0.9), (0.5, 0.0), (1.0, 1.0)] {
+            check(
+                &Generator::new(25, 25)
+                    .seed(seed)
+                    .digit_density(digits)
+                    .symbol_density(symbols)
+                    .gear_probability(0.5)
+                    .max_digits(9),
+            );
+        }
+    }
+}
+
+#[test]
+fn long_numbers() {
+    let mut answers = Vec::new();
+    for seed in 0..10 {
+        for max_digits in [12, 19] {
+            let generator = Generator::new(60, 20)
+                .seed(seed)
+                .max_digits(max_digits)
+                .digit_density(0.3)
+                .symbol_density(0.3);
+            check(&generator);
+            let generated = generator.generate();
+            answers.push(generated.part_sum);
+            answers.push(generated.gear_ratio_sum);
+        }
+    }
+    // Both answers that fit and answers that overflow were checked
+    assert!(answers
+        .iter()
+        .any(|answer| answer.is_some_and(|sum| sum > u32::MAX.into())));
+    assert!(answers.iter().any(|answer| answer.is_none()));
+
+    let generated = Generator::new(60, 20)
+        .max_digits(40)
+        .digit_density(1.0)
+        .generate();
+    let longest = generated
+        .lines()
+        .flat_map(|line| line.split(|c: char| !c.is_ascii_digit()))
+        .map(str::len)
+        .max();
+    assert_eq!(longest, Some(19));
+}
+
+#[test]
+fn gears_only_and_odd_shapes() {
+    for (width, height) in [(1, 1), (1, 50), (50, 1), (0, 3), (3, 0)] {
+        for seed in 0..10 {
+            check(
+                &Generator::new(width, height)
+                    .seed(seed)
+                    .symbols(['*'])
+                    .symbol_density(0.5),
+            );
+        }
+    }
+}
+
+#[test]
+fn same_seed_same_schematic() {
+    let generator = Generator::new(30, 30).seed(7);
+    assert_eq!(generator.generate(), generator.generate());
+    assert_ne!(generator.generate(), generator.clone().seed(8).generate());
+}
+
+#[test]
+fn custom_symbols_with_a_matching_config() {
+    let generated = Generator::new(30, 30)
+        .seed(3)
+        .symbols(['a', 'b'])
+        .symbol_density(0.3)
+        .generate();
+    let config = SchematicConfig::with_symbols(['a', 'b', '*']);
+    let schematic = Schematic::parse_with(generated.lines(), &config).unwrap();
+    let part_sum: u64 = schematic.get_valid_part_numbers().iter().sum();
+    assert_eq!(Some(part_sum), generated.part_sum);
+}
@@ -0,0 +1,140 @@
+use aoc_2023::engine::{Part, PartId, Schematic, Symbol, Vec2};
+
+const EXAMPLE: [&str; 10] = [
+    "467..114..",
+    "...*......",
+    "..35..633.",
+    "......#...",
+    "617*......",
+    ".....+.58.",
+    "..592.....",
+    "......755.",
+    "...$.*....",
+    ".664.598..",
+];
+
+fn id(x: i64, y: i64, len: u64) -> PartId {
+    PartId {
+        start: Vec2::new(x, y),
+        len,
+    }
+}
+
+fn sorted(parts: impl Iterator<Item = Part>) -> Vec<(PartId, u64)> {
+    let mut parts: Vec<_> = parts.map(|part| (part.id, part.value)).collect();
+    parts.sort_by_key(|(id, _)| (id.start.y, id.start.x));
+    parts
+}
+
+#[test]
+fn example_answers() {
+    let schematic = Schematic::parse(EXAMPLE.into_iter()).unwrap();
+    let graph = schematic.graph();
+    assert_eq!(graph.parts().count(), 10);
+    assert_eq!(graph.symbols().count(), 6);
+    assert_eq!(
+        graph.valid_parts().map(|part| part.value).sum::<u64>(),
+        4361
+    );
+    assert_eq!(
+        schematic
+            .get_gears()
+            .iter()
+            .map(|gear| gear.value.unwrap())
+            .sum::<u64>(),
+        467835
+    );
+}
+
+#[test]
+fn adjacency_in_both_directions() {
+    let schematic = Schematic::parse(EXAMPLE.into_iter()).unwrap();
+    let graph = schematic.graph();
+
+    assert_eq!(graph.symbols_adjacent_to(id(0, 0, 3)), &[Vec2::new(3, 1)]);
+    assert_eq!(
+        graph.parts_adjacent_to(Vec2::new(3, 1)),
+        &[id(0, 0, 3), id(2, 2, 2)]
+    );
+    assert_eq!(graph.parts_adjacent_to(Vec2::new(5, 5)), &[id(2, 6, 3)]);
+    assert_eq!(graph.symbol(Vec2::new(5, 5)), Some(Symbol('+')));
+
+    // Parts without symbols, cells that aren't symbols and ids that aren't parts have no edges
+    assert!(graph.symbols_adjacent_to(id(5, 0, 3)).is_empty());
+    assert!(graph.parts_adjacent_to(Vec2::new(0, 1)).is_empty());
+    assert!(graph.symbols_adjacent_to(id(0, 0, 2)).is_empty());
+    assert_eq!(graph.part(id(0, 0, 2)), None);
+    assert_eq!(graph.symbol(Vec2::new(0, 1)), None);
+}
+
+#[test]
+fn orphan_parts_touch_no_symbol() {
+    let schematic = Schematic::parse(EXAMPLE.into_iter()).unwrap();
+    let graph = schematic.graph();
+    assert_eq!(
+        sorted(graph.orphan_parts()),
+        vec![(id(5, 0, 3), 114), (id(7, 5, 2), 58)]
+    );
+    assert_eq!(
+        graph.valid_parts().count() + graph.orphan_parts().count(),
+        graph.parts().count()
+    );
+}
+
+#[test]
+fn duplicate_values_are_separate_parts() {
+    let schematic = Schematic::parse(["12.12", "..*..", "12..."].into_iter()).unwrap();
+    let graph = schematic.graph();
+    assert_eq!(
+        sorted(graph.parts()),
+        vec![(id(0, 0, 2), 12), (id(3, 0, 2), 12), (id(0, 2, 2), 12)]
+    );
+    assert_eq!(
+        graph.parts_adjacent_to(Vec2::new(2, 1)),
+        &[id(0, 0, 2), id(3, 0, 2), id(0, 2, 2)]
+    );
+    assert_eq!(schematic.get_valid_part_numbers(), vec![12, 12, 12]);
+    // Three parts, so not a gear, even though they all have the same value
+    assert!(schematic.get_gears().is_empty());
+
+    let schematic = Schematic::parse(["12.12", "..*.."].into_iter()).unwrap();
+    let gears = schematic.get_gears();
+    assert_eq!(gears.len(), 1);
+    assert_eq!(gears[0].value, Some(144));
+}
+
+#[test]
+fn edges_are_in_reading_order() {
+    // Symbols are found from the parts' cells, so they aren't discovered in reading order
+    let schematic = Schematic::parse(["$..#", ".55.", "%..&"].into_iter()).unwrap();
+    assert_eq!(
+        schematic.graph().symbols_adjacent_to(id(1, 1, 2)),
+        &[
+            Vec2::new(0, 0),
+            Vec2::new(3, 0),
+            Vec2::new(0, 2),
+            Vec2::new(3, 2)
+        ]
+    );
+
+    let schematic = Schematic::parse(["1.2", "3*4", "5.6"].into_iter()).unwrap();
+    assert_eq!(
+        schematic.graph().parts_adjacent_to(Vec2::new(1, 1)),
+        &[
+            id(0, 0, 1),
+            id(2, 0, 1),
+            id(0, 1, 1),
+            id(2, 1, 1),
+            id(0, 2, 1),
+            id(2, 2, 1)
+        ]
+    );
+}
+
+#[test]
+fn a_part_touching_a_symbol_twice_has_one_edge() {
+    let schematic = Schematic::parse(["123", ".#."].into_iter()).unwrap();
+    let graph = schematic.graph();
+    assert_eq!(graph.symbols_adjacent_to(id(0, 0, 3)), &[Vec2::new(1, 1)]);
+    assert_eq!(graph.parts_adjacent_to(Vec2::new(1, 1)), &[id(0, 0, 3)]);
+}
@@ -0,0 +1,114 @@
+use aoc_2023::{
+    geometry::Vec2,
+    grid::{Grid, GridError},
+};
+
+fn numbers() -> Grid<u32> {
+    Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+}
+
+fn positions<'a, I: Iterator<Item = (Vec2, &'a char)>>(cells: I) -> Vec<(i64, i64, char)> {
+    cells.map(|(pos, c)| (pos.x, pos.y, *c)).collect()
+}
+
+#[test]
+fn ragged_rows_are_rejected() {
+    let err = Grid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5]]).unwrap_err();
+    assert!(matches!(
+        err,
+        GridError::RaggedRow {
+            row: 2,
+            expected: 2,
+            found: 1
+        }
+    ));
+    assert_eq!(err.to_string(), "row 2 has 1 cells, expected 2");
+
+    let empty = Grid::<u32>::from_rows(Vec::new()).unwrap();
+    assert_eq!((empty.width(), empty.height()), (0, 0));
+}
+
+#[test]
+fn parse_pads_short_lines() {
+    let grid = Grid::parse(["ab", "", "cde"].into_iter(), '.');
+    assert_eq!((grid.width(), grid.height()), (3, 3));
+    assert_eq!(grid.to_string(), "ab.\n...\ncde");
+    assert_eq!(grid[Vec2::new(2, 0)], '.');
+}
+
+#[test]
+fn get_and_index() {
+    let mut grid = numbers();
+    assert_eq!(grid.get(Vec2::new(2, 1)), Some(&6));
+    assert_eq!(grid.get(Vec2::new(3, 0)), None);
+    assert_eq!(grid.get(Vec2::new(0, -1)), None);
+    grid[Vec2::new(1, 0)] = 20;
+    assert_eq!(grid.row(0), Some(&[1, 20, 3][..]));
+    assert_eq!(grid.row(2), None);
+}
+
+#[test]
+#[should_panic(expected = "[0, 2] is outside of the grid")]
+fn index_outside_panics() {
+    let _ = numbers()[Vec2::new(0, 2)];
+}
+
+#[test]
+fn neighbours_are_clipped_at_the_edges() {
+    let grid = Grid::parse(["abc", "def", "ghi"].into_iter(), '.');
+    assert_eq!(
+        positions(grid.neighbours4(Vec2::new(0, 0))),
+        vec![(1, 0, 'b'), (0, 1, 'd')]
+    );
+    assert_eq!(
+        positions(grid.neighbours8(Vec2::new(2, 2))),
+        vec![(2, 1, 'f'), (1, 2, 'h'), (1, 1, 'e')]
+    );
+    assert_eq!(grid.neighbours8(Vec2::new(1, 1)).count(), 8);
+    assert_eq!(grid.neighbours4(Vec2::new(1, 0)).count(), 3);
+    // Positions outside of the grid only see the cells next to them that are inside
+    assert_eq!(
+        positions(grid.neighbours8(Vec2::new(-1, -1))),
+        vec![(0, 0, 'a')]
+    );
+}
+
+#[test]
+fn rows_and_columns() {
+    let grid = numbers();
+    let rows: Vec<_> = grid.rows().collect();
+    assert_eq!(rows, vec![&[1, 2, 3][..], &[4, 5, 6][..]]);
+
+    assert_eq!(grid.column(1).unwrap().collect::<Vec<_>>(), vec![&2, &5]);
+    assert!(grid.column(3).is_none());
+    let columns: Vec<Vec<_>> = grid
+        .columns()
+        .map(|column| column.copied().collect())
+        .collect();
+    assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+}
+
+#[test]
+fn iter_and_map() {
+    let grid = numbers();
+    let cells: Vec<_> = grid.iter().map(|(pos, n)| (pos.x, pos.y, *n)).collect();
+    assert_eq!(
+        cells,
+        vec![
+            (0, 0, 1),
+            (1, 0, 2),
+            (2, 0, 3),
+            (0, 1, 4),
+            (1, 1, 5),
+            (2, 1, 6)
+        ]
+    );
+    assert_eq!(grid.map(|n| n * 10).to_string(), "102030\n405060");
+}
+
+#[test]
+fn display_writes_rows_on_separate_lines() {
+    assert_eq!(numbers().to_string(), "123\n456");
+    assert_eq!(Grid::new(2, 1, '#').to_string(), "##");
+    assert_eq!(Grid::<char>::from_rows(Vec::new()).unwrap().to_string(), "");
+}
@@ -0,0 +1,92 @@
+use aoc_2023::{
+    geometry::Vec2,
+    hex::{Hex, HexDirection, HexGrid, HexParseError, OffsetLayout},
+};
+
+#[test]
+fn distances_and_neighbours() {
+    let origin = Hex::ZERO;
+    assert!(origin.neighbours().all(|hex| hex.distance(origin) == 1));
+    assert_eq!(Hex::new(3, -1).distance(Hex::new(-2, 2)), 5);
+    assert_eq!(Hex::new(2, -3).s(), 1);
+    assert_eq!(Hex::from_cube(1, 1, 1), None);
+    assert_eq!(HexDirection::NE.opposite(), HexDirection::SW);
+    assert_eq!(HexDirection::E.turn(-1), HexDirection::NE);
+}
+
+#[test]
+fn rings_and_spirals() {
+    let centre = Hex::new(2, -1);
+    for radius in 0..5 {
+        let ring = centre.ring(radius);
+        assert_eq!(ring.len(), (6 * radius).max(1) as usize);
+        assert!(ring.iter().all(|hex| hex.distance(centre) == radius as i64));
+        // Each hex of the ring touches the next one
+        assert!(ring.windows(2).all(|pair| pair[0].distance(pair[1]) == 1));
+    }
+
+    let spiral = centre.spiral(3);
+    assert_eq!(spiral.len(), 37);
+    assert_eq!(spiral[0], centre);
+}
+
+#[test]
+fn rotation() {
+    let hex = Hex::new(1, 0);
+    assert_eq!(hex.rotate_right(), Hex::new(0, 1));
+    assert_eq!(hex.rotate_left(), Hex::new(1, -1));
+    assert_eq!(hex.rotate_right().rotate_left(), hex);
+
+    let centre = Hex::new(-3, 2);
+    let hex = Hex::new(1, 4);
+    assert_eq!(hex.rotate_around(centre, 6), hex);
+    assert_eq!(hex.rotate_around(centre, -2), hex.rotate_around(centre, 4));
+    assert_eq!(
+        hex.rotate_around(centre, 3).distance(centre),
+        hex.distance(centre)
+    );
+}
+
+#[test]
+fn offset_coordinates_round_trip() {
+    for layout in [OffsetLayout::OddR, OffsetLayout::EvenR] {
+        for hex in Hex::ZERO.spiral(4) {
+            assert_eq!(Hex::from_offset(hex.to_offset(layout), layout), hex);
+        }
+    }
+    assert_eq!(
+        Hex::new(0, 1).to_offset(OffsetLayout::OddR),
+        Vec2::new(0, 1)
+    );
+    assert_eq!(
+        Hex::new(0, 1).to_offset(OffsetLayout::EvenR),
+        Vec2::new(1, 1)
+    );
+}
+
+#[test]
+fn parse_text_layouts() {
+    let text = ["a b c", " d e f", "g h i"];
+    let grid = HexGrid::parse(text.into_iter(), OffsetLayout::OddR).unwrap();
+    assert_eq!(grid.len(), 9);
+    let e = Hex::from_offset(Vec2::new(1, 1), OffsetLayout::OddR);
+    assert_eq!(grid[e], 'e');
+
+    let mut around: Vec<char> = grid.neighbours(e).map(|(_, c)| *c).collect();
+    around.sort();
+    assert_eq!(around, vec!['b', 'c', 'd', 'f', 'h', 'i']);
+    assert_eq!(grid.to_string(), text.join("\n"));
+
+    let even = [" a b", "c d"];
+    let grid = HexGrid::parse(even.into_iter(), OffsetLayout::EvenR).unwrap();
+    assert_eq!(grid.to_string(), even.join("\n"));
+
+    assert_eq!(
+        HexGrid::parse(["a  b"].into_iter(), OffsetLayout::OddR),
+        Err(HexParseError::Misaligned {
+            line: 0,
+            column: 3,
+            c: 'b'
+        })
+    );
+}
@@ -0,0 +1,160 @@
+use aoc_2023::{
+    input::{self, Lines},
+    Opt,
+};
+use clap::Parser;
+use flate2::{write::GzEncoder, Compression};
+use std::{
+    io::{BufRead, Cursor, Write},
+    path::PathBuf,
+};
+
+fn lines(s: &str) -> Lines<Cursor<&[u8]>> {
+    Lines::new(Cursor::new(s.as_bytes()))
+}
+
+fn collect<I: Iterator<Item = std::io::Result<T>>, T>(iter: I) -> Vec<T> {
+    iter.collect::<std::io::Result<_>>().unwrap()
+}
+
+#[test]
+fn line_terminators_are_removed() {
+    assert_eq!(collect(lines("a\nb\r\nc\n")), vec!["a", "b", "c"]);
+    assert_eq!(collect(lines("")), Vec::<String>::new());
+}
+
+#[test]
+fn last_line_without_a_newline() {
+    assert_eq!(collect(lines("a\r\nb")), vec!["a", "b"]);
+    assert_eq!(collect(lines("a\n\nb")), vec!["a", "", "b"]);
+}
+
+#[test]
+fn only_one_carriage_return_is_part_of_the_terminator() {
+    assert_eq!(collect(lines("a\r\r\nb\r")), vec!["a\r", "b\r"]);
+}
+
+#[test]
+fn trimmed_lines() {
+    assert_eq!(
+        collect(lines("  a b \r\n\t\n c\n").trimmed()),
+        vec!["a b", "", "c"]
+    );
+}
+
+#[test]
+fn paragraphs_are_split_on_blank_lines() {
+    let text = "\n\na\nb\n\n\n  \nc\r\n\r\nd\ne";
+    assert_eq!(
+        collect(lines(text).paragraphs()),
+        vec![vec!["a", "b"], vec!["c"], vec!["d", "e"]]
+    );
+    assert_eq!(
+        collect(lines("a\n\n").paragraphs()),
+        vec![vec!["a".to_string()]]
+    );
+    assert_eq!(
+        collect(lines("\n\n").paragraphs()),
+        Vec::<Vec<String>>::new()
+    );
+}
+
+#[test]
+fn from_string_reads_like_a_file() {
+    assert_eq!(
+        collect(Lines::new(input::from_string("x\r\ny"))),
+        vec!["x", "y"]
+    );
+}
+
+// A file in a directory of its own, which is removed when the test is done with it
+struct TempFile {
+    dir: PathBuf,
+    path: PathBuf,
+}
+
+impl TempFile {
+    fn new(name: &str, contents: &[u8]) -> TempFile {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{}-{name}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        TempFile { dir, path }
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn gzip(s: &str) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(s.as_bytes()).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn read(reader: Box<dyn BufRead>) -> Vec<String> {
+    collect(Lines::new(reader))
+}
+
+#[test]
+fn decompress_detects_gzip_and_zstd() {
+    let text = "467..114..\n...*......\n";
+    let zstd = zstd::encode_all(text.as_bytes(), 0).unwrap();
+    for bytes in [text.as_bytes().to_vec(), gzip(text), zstd] {
+        let reader = input::decompress(Cursor::new(bytes)).unwrap();
+        assert_eq!(read(reader), vec!["467..114..", "...*......"]);
+    }
+}
+
+#[test]
+fn decompress_leaves_empty_input_alone() {
+    let reader = input::decompress(Cursor::new(Vec::new())).unwrap();
+    assert_eq!(read(reader), Vec::<String>::new());
+}
+
+#[test]
+fn open_reads_the_same_with_and_without_mmap() {
+    let plain = TempFile::new("plain.txt", b"a\r\nb\n");
+    let gzipped = TempFile::new("gzipped.txt.gz", &gzip("a\r\nb\n"));
+    for file in [&plain, &gzipped] {
+        for mmap in [false, true] {
+            assert_eq!(read(input::open(&file.path, mmap).unwrap()), vec!["a", "b"]);
+        }
+    }
+    assert!(input::open(&plain.dir.join("missing.txt"), false).is_err());
+}
+
+#[test]
+fn chained_readers_are_read_in_sequence() {
+    let readers = vec![
+        input::from_string("a\nb"),
+        input::from_string(""),
+        input::from_string("c\n"),
+        input::decompress(Cursor::new(gzip("d"))).unwrap(),
+    ];
+    // `b` and `c` stay separate lines even though the first reader doesn't end in a newline
+    assert_eq!(collect(Lines::chain(readers)), vec!["a", "b", "c", "d"]);
+}
+
+#[test]
+fn opt_reads_files_in_sequence() {
+    let first = TempFile::new("first.txt", b"1\n2");
+    let second = TempFile::new("second.txt", b"3\n");
+    let opt = Opt::parse_from([
+        "aoc".as_ref(),
+        first.path.as_os_str(),
+        second.path.as_os_str(),
+        "--mmap".as_ref(),
+    ]);
+    assert_eq!(aoc_2023::read_lines(&opt).unwrap(), vec!["1", "2", "3"]);
+}
+
+#[test]
+fn opt_input_string_replaces_the_input() {
+    let opt = Opt::parse_from(["aoc", "--input-string", "a\nb\n"]);
+    assert_eq!(aoc_2023::read_lines(&opt).unwrap(), vec!["a", "b"]);
+    assert!(Opt::try_parse_from(["aoc", "file.txt", "--input-string", "a"]).is_err());
+}
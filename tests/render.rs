@@ -0,0 +1,61 @@
+use aoc_2023::{
+    engine::Schematic,
+    render::{render, RenderStyle},
+};
+
+const EXAMPLE: [&str; 10] = [
+    "467..114..",
+    "...*......",
+    "..35..633.",
+    "......#...",
+    "617*......",
+    ".....+.58.",
+    "..592.....",
+    "......755.",
+    "...$.*....",
+    ".664.598..",
+];
+
+#[test]
+fn plain_marks_parts_and_gears() {
+    let schematic = Schematic::parse(EXAMPLE.into_iter()).unwrap();
+    let expected = [
+        "[467]..{114}..",
+        "...<*>......",
+        "..[35]..[633].",
+        "......#...",
+        "[617]*......",
+        ".....+.{58}.",
+        "..[592].....",
+        "......[755].",
+        "...$.<*>....",
+        ".[664].[598]..",
+        "Gear * at [3, 1]: parts 467, 35 = 16345",
+        "Gear * at [5, 8]: parts 755, 598 = 451490",
+    ];
+    assert_eq!(render(&schematic, RenderStyle::Plain), expected.join("\n"));
+}
+
+#[test]
+fn plain_keeps_leading_zeroes_and_shows_overflowing_gears() {
+    let lines = ["007.", "#...", "", "12345678901*12345678901"];
+    let schematic = Schematic::parse(lines.into_iter()).unwrap();
+    let expected = [
+        format!("[007]{}", ".".repeat(20)),
+        format!("#{}", ".".repeat(22)),
+        ".".repeat(23),
+        "[12345678901]<*>[12345678901]".into(),
+        "Gear * at [11, 3]: parts 12345678901, 12345678901 = overflow".into(),
+    ];
+    assert_eq!(render(&schematic, RenderStyle::Plain), expected.join("\n"));
+}
+
+#[test]
+fn ansi_colours_parts_and_gears() {
+    let schematic = Schematic::parse(["1*2.3"].into_iter()).unwrap();
+    assert_eq!(
+        render(&schematic, RenderStyle::Ansi),
+        "\x1b[32m1\x1b[0m\x1b[1;33m*\x1b[0m\x1b[32m2\x1b[0m.\x1b[31m3\x1b[0m\n\
+         \x1b[1;33mGear * at [1, 0]: parts 1, 2 = 2\x1b[0m"
+    );
+}
@@ -0,0 +1,38 @@
+use aoc_2023::{cache::Answers, report::Report};
+use serde_json::json;
+
+#[test]
+fn json_has_answers_by_part_and_warnings() {
+    let mut report = Report::new(3);
+    report.answer(1, "Sum", 4361);
+    report.answer(2, "Gear Ratio Sum", 467835);
+    report.detail("only for humans");
+    report.warn("line 2 has no digits");
+
+    let json = report.to_json();
+    assert_eq!(json["day"], json!(3));
+    assert_eq!(json["answers"], json!({"part1": 4361, "part2": 467835}));
+    assert_eq!(json["warnings"], json!(["line 2 has no digits"]));
+    assert!(json["elapsed_ms"].as_f64().unwrap() >= 0.);
+    assert_eq!(json.as_object().unwrap().len(), 4);
+}
+
+#[test]
+fn check_warns_about_mismatched_answers_only() {
+    let mut report = Report::new(3);
+    report.answer(1, "Sum", 4361);
+    report.answer(2, "Gear Ratio Sum", 1);
+
+    // Part 3 has no answer, so there is nothing to compare it with
+    let expected = Answers::from([(1, 4361), (2, 467835), (3, 7)]);
+    report.check(&expected);
+    assert_eq!(
+        report.to_json()["warnings"],
+        json!(["part 2 answer 1 does not match the expected 467835"])
+    );
+
+    let mut matching = Report::new(3);
+    matching.answer(1, "Sum", 4361);
+    matching.check(&expected);
+    assert_eq!(matching.to_json()["warnings"], json!([]));
+}
@@ -0,0 +1,187 @@
+use aoc_2023::scaffold::{add_module, Scaffold};
+use std::path::{Path, PathBuf};
+
+const LIB: &str = "use std::io;\n\npub mod alpha;\npub mod gamma;\n\npub fn main() {}\n";
+
+// A crate root in a directory of its own, which is removed when the test is done with it
+struct TempCrate {
+    root: PathBuf,
+}
+
+impl TempCrate {
+    fn new(name: &str) -> TempCrate {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src").join("bin")).unwrap();
+        std::fs::write(root.join("src").join("lib.rs"), LIB).unwrap();
+        TempCrate { root }
+    }
+
+    fn read(&self, path: &str) -> String {
+        std::fs::read_to_string(self.root.join(path)).unwrap()
+    }
+}
+
+impl Drop for TempCrate {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    std::fs::create_dir_all(to).unwrap();
+    for entry in std::fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let dest = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &dest);
+        } else {
+            std::fs::copy(entry.path(), dest).unwrap();
+        }
+    }
+}
+
+#[test]
+fn add_module_keeps_the_list_sorted() {
+    assert_eq!(
+        add_module(LIB, "beta").unwrap(),
+        "use std::io;\n\npub mod alpha;\npub mod beta;\npub mod gamma;\n\npub fn main() {}\n"
+    );
+    assert_eq!(
+        add_module(LIB, "aardvark").unwrap(),
+        "use std::io;\n\npub mod aardvark;\npub mod alpha;\npub mod gamma;\n\npub fn main() {}\n"
+    );
+    assert_eq!(
+        add_module(LIB, "zeta").unwrap(),
+        "use std::io;\n\npub mod alpha;\npub mod gamma;\npub mod zeta;\n\npub fn main() {}\n"
+    );
+}
+
+#[test]
+fn add_module_rejects_duplicates_and_missing_lists() {
+    let err = add_module(LIB, "gamma").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "module gamma is already declared in lib.rs"
+    );
+
+    let err = add_module("use std::io;\n\nmod private;\n", "beta").unwrap_err();
+    assert_eq!(err.to_string(), "could not find the pub mod list in lib.rs");
+}
+
+#[test]
+fn new_rejects_bad_names_and_days() {
+    let root = Path::new("unused");
+    for name in [
+        "../../evil",
+        "",
+        "_",
+        "Day05",
+        "5day",
+        "day-5",
+        "day 5",
+        "mod",
+        "fn",
+    ] {
+        assert!(
+            Scaffold::new(root, 5, Some(name.into())).is_err(),
+            "{name:?} was accepted"
+        );
+    }
+    for day in [0, 26, 99] {
+        assert!(
+            Scaffold::new(root, day, None).is_err(),
+            "day {day} was accepted"
+        );
+    }
+
+    let scaffold = Scaffold::new(root, 5, None).unwrap();
+    assert_eq!(scaffold.module_path(), root.join("src").join("day05.rs"));
+    assert_eq!(
+        scaffold.bin_path(),
+        root.join("src").join("bin").join("aoc5.rs")
+    );
+    let scaffold = Scaffold::new(root, 25, Some("_seeds2".into())).unwrap();
+    assert_eq!(scaffold.module_path(), root.join("src").join("_seeds2.rs"));
+}
+
+#[test]
+fn generate_writes_module_bin_and_lib() {
+    let temp = TempCrate::new("generate");
+    let scaffold = Scaffold::new(&temp.root, 5, Some("beta".into())).unwrap();
+    assert_eq!(
+        scaffold.generate().unwrap(),
+        vec![
+            scaffold.module_path(),
+            scaffold.bin_path(),
+            scaffold.lib_path()
+        ]
+    );
+
+    assert!(temp
+        .read("src/lib.rs")
+        .contains("pub mod alpha;\npub mod beta;\npub mod gamma;"));
+    assert!(temp
+        .read("src/beta.rs")
+        .contains("day 5 puzzle description"));
+    let bin = temp.read("src/bin/aoc5.rs");
+    assert!(bin.contains("use aoc_2023::{beta::Puzzle"));
+    assert!(bin.contains("Report::new(5)"));
+}
+
+#[test]
+fn generate_never_overwrites() {
+    let temp = TempCrate::new("overwrite");
+    std::fs::write(temp.root.join("src/beta.rs"), "existing module").unwrap();
+    std::fs::write(temp.root.join("src/bin/aoc6.rs"), "existing bin").unwrap();
+
+    // Either file existing stops everything, including the change to lib.rs
+    let module = Scaffold::new(&temp.root, 5, Some("beta".into())).unwrap();
+    assert!(module.generate().is_err());
+    let bin = Scaffold::new(&temp.root, 6, Some("delta".into())).unwrap();
+    assert!(bin.generate().is_err());
+    // And so does a module that is already declared
+    let declared = Scaffold::new(&temp.root, 7, Some("gamma".into())).unwrap();
+    assert!(declared.generate().is_err());
+
+    assert_eq!(temp.read("src/lib.rs"), LIB);
+    assert_eq!(temp.read("src/beta.rs"), "existing module");
+    assert_eq!(temp.read("src/bin/aoc6.rs"), "existing bin");
+    assert!(!temp.root.join("src/bin/aoc5.rs").exists());
+    assert!(!temp.root.join("src/delta.rs").exists());
+    assert!(!temp.root.join("src/bin/aoc7.rs").exists());
+    assert!(!temp.root.join("src/gamma.rs").exists());
+}
+
+// Generates a day in a copy of this crate and checks that it builds, tests included. The target
+// directory is kept between runs so only the first one has to check the dependencies
+#[test]
+fn generated_day_compiles() {
+    let temp = TempCrate::new("compile");
+    let source = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for dir in ["src", "templates", "benches"] {
+        copy_dir(&source.join(dir), &temp.root.join(dir));
+    }
+    for file in ["Cargo.toml", "Cargo.lock"] {
+        std::fs::copy(source.join(file), temp.root.join(file)).unwrap();
+    }
+
+    let scaffold = Scaffold::new(&temp.root, 25, None).unwrap();
+    scaffold.generate().unwrap();
+
+    let output = std::process::Command::new(env!("CARGO"))
+        .args(["check", "--offline", "--quiet", "--profile", "test"])
+        .args(["--lib", "--bin", "aoc25"])
+        .current_dir(&temp.root)
+        .env(
+            "CARGO_TARGET_DIR",
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join("scaffold"),
+        )
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
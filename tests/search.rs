@@ -0,0 +1,212 @@
+use aoc_2023::{
+    geometry::Vec2,
+    grid::Grid,
+    search::{astar, bfs, dijkstra},
+};
+
+fn grid(lines: &[&str]) -> Grid<char> {
+    Grid::parse(lines.iter().copied(), '#')
+}
+
+fn cells(nodes: &[Vec2]) -> Vec<(i64, i64)> {
+    nodes.iter().map(|pos| (pos.x, pos.y)).collect()
+}
+
+fn open(c: &char) -> bool {
+    *c != '#'
+}
+
+// Digits cost that much to step into, anything else 1
+fn cost(c: &char) -> u64 {
+    c.to_digit(10).map_or(1, u64::from)
+}
+
+#[test]
+fn bfs_breaks_ties_by_neighbour_order() {
+    let grid = grid(&["...", "...", "..."]);
+    let result = grid.bfs(Vec2::new(0, 0), Vec2::new(2, 2), open);
+
+    // Neighbours come north, east, south, west, so the path goes east before it goes south
+    let path = result.path.unwrap();
+    assert_eq!(
+        cells(&path.nodes),
+        vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]
+    );
+    assert_eq!(path.cost, 4);
+    assert_eq!(
+        cells(&result.visited),
+        vec![
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (2, 0),
+            (1, 1),
+            (0, 2),
+            (2, 1),
+            (1, 2),
+            (2, 2)
+        ]
+    );
+}
+
+#[test]
+fn dijkstra_breaks_ties_by_discovery_order() {
+    let grid = grid(&["...", "...", "..."]);
+    let result = grid.dijkstra(Vec2::new(0, 0), Vec2::new(2, 2), open, cost);
+
+    let path = result.path.unwrap();
+    assert_eq!(
+        cells(&path.nodes),
+        vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]
+    );
+    assert_eq!(path.cost, 4);
+    assert_eq!(
+        cells(&result.visited),
+        vec![
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (2, 0),
+            (1, 1),
+            (0, 2),
+            (2, 1),
+            (1, 2),
+            (2, 2)
+        ]
+    );
+}
+
+#[test]
+fn dijkstra_takes_the_cheapest_path_over_the_shortest() {
+    let grid = grid(&[".9.", "...", "..."]);
+    let result = grid.dijkstra(Vec2::new(0, 0), Vec2::new(2, 0), open, cost);
+
+    let path = result.path.unwrap();
+    assert_eq!(
+        cells(&path.nodes),
+        vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)]
+    );
+    assert_eq!(path.cost, 4);
+    // The expensive cell is never expanded, since the goal is reached for less
+    assert!(!result.visited.contains(&Vec2::new(1, 0)));
+}
+
+#[test]
+fn astar_finds_the_same_cost_and_expands_less() {
+    let grid = grid(&["......", ".####.", ".#..#.", ".#..#.", "......", "......"]);
+    let (start, goal) = (Vec2::new(0, 0), Vec2::new(5, 5));
+    let slow = grid.dijkstra(start, goal, open, cost);
+    let fast = grid.astar(start, goal, open, cost);
+
+    assert_eq!(fast.path.as_ref().unwrap().cost, 10);
+    assert_eq!(slow.path.as_ref().unwrap().cost, 10);
+    assert!(fast.visited.len() < slow.visited.len());
+    assert_eq!(fast.visited.first(), Some(&start));
+    assert_eq!(fast.visited.last(), Some(&goal));
+    // The same inputs always give the same path
+    assert_eq!(grid.astar(start, goal, open, cost), fast);
+}
+
+#[test]
+fn astar_with_a_consistent_heuristic_on_a_graph() {
+    // 0 -> 1 -> 3 costs 2 + 2, 0 -> 2 -> 3 costs 1 + 4
+    let edges = |n: &u32| match n {
+        0 => vec![(1, 2), (2, 1)],
+        1 => vec![(3, 2)],
+        2 => vec![(3, 4)],
+        _ => vec![],
+    };
+    let heuristic = |n: &u32| [3, 2, 3, 0][*n as usize];
+    let result = astar(
+        0,
+        |n| *n == 3,
+        |n| edges(n).into_iter().map(|(next, _)| next),
+        |n, next| edges(n).iter().find(|(m, _)| m == next).unwrap().1,
+        heuristic,
+    );
+
+    let path = result.path.unwrap();
+    assert_eq!(path.nodes, vec![0, 1, 3]);
+    assert_eq!(path.cost, 4);
+    // 1, 2 and then 3 all have a priority of 4, so they are expanded in the order they were found
+    assert_eq!(result.visited, vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn no_path() {
+    let walled = grid(&["..#..", "..#..", "..#.."]);
+    let (start, goal) = (Vec2::new(0, 0), Vec2::new(4, 0));
+
+    let result = walled.bfs(start, goal, open);
+    assert_eq!(result.path, None);
+    // Everything reachable is expanded before giving up
+    assert_eq!(result.visited.len(), 6);
+    assert_eq!(walled.dijkstra(start, goal, open, cost).path, None);
+    let result = walled.astar(start, goal, open, cost);
+    assert_eq!(result.path, None);
+    assert_eq!(result.visited.len(), 6);
+
+    // A goal outside of the grid can't be reached either
+    let open_grid = grid(&["..", ".."]);
+    assert_eq!(open_grid.bfs(start, Vec2::new(5, 5), open).path, None);
+}
+
+#[test]
+fn start_is_the_goal() {
+    let result = bfs(7, |n| *n == 7, |n| vec![n + 1]);
+    let path = result.path.unwrap();
+    assert_eq!((path.nodes, path.cost), (vec![7], 0));
+    assert_eq!(result.visited, vec![7]);
+
+    let result = dijkstra(7, |n| *n == 7, |n| vec![n + 1], |_, _| 5);
+    assert_eq!(result.path.unwrap().cost, 0);
+}
+
+#[test]
+fn search_on_a_graph_with_no_way_to_the_goal() {
+    // Counting up from 0 and stopping at 5 never reaches 10
+    let result = bfs(0, |n| *n == 10, |n| (*n < 5).then_some(n + 1));
+    assert_eq!(result.path, None);
+    assert_eq!(result.visited, vec![0, 1, 2, 3, 4, 5]);
+
+    let result = dijkstra(0, |n| *n == 10, |n| (*n < 5).then_some(n + 1), |_, _| 1);
+    assert_eq!(result.path, None);
+    assert_eq!(result.visited, vec![0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn costs_that_overflow_are_skipped() {
+    // 0 -> 1 -> 2 costs u64::MAX + 1, so only 0 -> 3 -> 2 can be taken
+    let edges = |n: &u32| match n {
+        0 => vec![(1, u64::MAX), (3, 1)],
+        1 => vec![(2, 1)],
+        3 => vec![(2, 5)],
+        _ => vec![],
+    };
+    let step = |n: &u32, next: &u32| edges(n).iter().find(|(m, _)| m == next).unwrap().1;
+    let next = |n: &u32| {
+        edges(n)
+            .into_iter()
+            .map(|(next, _)| next)
+            .collect::<Vec<_>>()
+    };
+
+    let path = dijkstra(0, |n| *n == 2, next, step).path.unwrap();
+    assert_eq!((path.nodes, path.cost), (vec![0, 3, 2], 6));
+
+    // Without the detour there is no path at all
+    let result = dijkstra(
+        0,
+        |n| *n == 2,
+        |n| next(n).into_iter().filter(|m| *m != 3),
+        step,
+    );
+    assert_eq!(result.path, None);
+    assert_eq!(result.visited, vec![0, 1]);
+
+    // A huge heuristic saturates the priority, so node 1 is just expanded last
+    let heuristic = |n: &u32| if *n == 1 { u64::MAX } else { 0 };
+    let result = astar(0, |n| *n == 2, next, step, heuristic);
+    assert_eq!(result.path.unwrap().cost, 6);
+    assert_eq!(result.visited, vec![0, 3, 2]);
+}
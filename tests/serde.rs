@@ -0,0 +1,66 @@
+#![cfg(feature = "serde")]
+
+use aoc_2023::{
+    block_game::Game,
+    engine::{Schematic, SchematicConfig, Vec2},
+    trie::Trie,
+};
+use serde_json::json;
+
+const EXAMPLE: [&str; 4] = ["467..114..", "...*......", "..35..633.", "......#..."];
+
+#[test]
+fn schematic_round_trips_through_json() {
+    let schematic = Schematic::parse(EXAMPLE.into_iter()).unwrap();
+    let value = serde_json::to_value(&schematic).unwrap();
+
+    assert_eq!(value["rows"][1], "...*......");
+    assert_eq!(value["background"], ".");
+    assert_eq!(
+        value["parts"][0],
+        json!({"id": {"start": {"x": 0, "y": 0}, "len": 3}, "value": 467, "valid": true})
+    );
+    assert_eq!(value["parts"][1]["valid"], false);
+    assert_eq!(
+        value["symbols"][0],
+        json!({"pos": {"x": 3, "y": 1}, "symbol": "*"})
+    );
+    assert_eq!(value["gears"][0]["value"], 467 * 35);
+
+    let back: Schematic = serde_json::from_value(value).unwrap();
+    assert_eq!(back, schematic);
+}
+
+#[test]
+fn custom_classifiers_become_sets() {
+    let config = SchematicConfig::with_classifier(|c| c == '#');
+    let schematic = Schematic::parse_with(EXAMPLE.into_iter(), &config).unwrap();
+    let value = serde_json::to_value(&schematic).unwrap();
+    assert_eq!(value["symbol_class"], json!({"set": ["#"]}));
+
+    let back: Schematic = serde_json::from_value(value).unwrap();
+    assert_eq!(back.get_valid_part_numbers(), vec![633]);
+}
+
+#[test]
+fn games_tries_and_vectors() {
+    let game = Game::parse("Game 3: 8 green, 6 blue, 20 red; 5 blue").unwrap();
+    assert_eq!(
+        serde_json::to_value(&game).unwrap(),
+        json!({
+            "game_id": 3,
+            "draws": [
+                {"num_red": 20, "num_blue": 6, "num_green": 8},
+                {"num_red": 0, "num_blue": 5, "num_green": 0},
+            ]
+        })
+    );
+
+    let trie = Trie::new(["two", "one", "three"].into_iter());
+    let value = serde_json::to_value(&trie).unwrap();
+    assert_eq!(value, json!(["one", "three", "two"]));
+    assert_eq!(serde_json::from_value::<Trie>(value).unwrap(), trie);
+
+    let pos: Vec2 = serde_json::from_str(r#"{"x": -1, "y": 2}"#).unwrap();
+    assert_eq!(pos, Vec2::new(-1, 2));
+}
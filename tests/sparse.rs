@@ -0,0 +1,47 @@
+use aoc_2023::{
+    geometry::{Bounds, Vec2},
+    grid::Grid,
+    sparse::SparseGrid,
+};
+
+#[test]
+fn bounds_follow_inserts_and_removes() {
+    let mut grid = SparseGrid::new();
+    assert_eq!(grid.bounds(), None);
+
+    grid.insert(Vec2::new(-3, 2), '#');
+    grid.insert(Vec2::new(4, -1), '#');
+    grid.insert(Vec2::new(0, 0), '#');
+    assert_eq!(
+        grid.bounds(),
+        Some(Bounds::new(Vec2::new(-3, -1), Vec2::new(4, 2)))
+    );
+
+    grid.remove(Vec2::new(-3, 2));
+    assert_eq!(
+        grid.bounds(),
+        Some(Bounds::new(Vec2::new(0, -1), Vec2::new(4, 0)))
+    );
+
+    grid.remove(Vec2::new(4, -1));
+    grid.remove(Vec2::new(0, 0));
+    assert_eq!(grid.bounds(), None);
+}
+
+#[test]
+fn round_trip_through_dense_grid() {
+    let dense = Grid::parse(["#..", "..#", ".#."].into_iter(), '.');
+    let sparse = SparseGrid::from_grid(&dense, Vec2::new(-1, -1), |c| *c == '#');
+    assert_eq!(sparse.len(), 3);
+    assert_eq!(sparse.get(Vec2::new(-1, -1)), Some(&'#'));
+    assert_eq!(sparse.to_string(), "#..\n..#\n.#.");
+    assert_eq!(
+        sparse.neighbours8(Vec2::new(0, 0)).count(),
+        3,
+        "every occupied cell touches the centre"
+    );
+
+    let (back, origin) = sparse.to_grid('.');
+    assert_eq!(origin, Vec2::new(-1, -1));
+    assert_eq!(back, dense);
+}
@@ -0,0 +1,59 @@
+use aoc_2023::{
+    geometry::{Bounds, Vec2},
+    spatial::{Span, SpanIndex},
+};
+
+fn values<'a, I: Iterator<Item = &'a Span<char>>>(spans: I) -> Vec<char> {
+    spans.map(|span| span.value).collect()
+}
+
+fn index() -> SpanIndex<char> {
+    // a: [0, 0]..[2, 0], b: [5, 0]..[14, 0], c: [1, 1], d: [3, 2]..[4, 2]
+    let mut index = SpanIndex::new();
+    index.insert(Vec2::new(5, 0), 10, 'b');
+    index.insert(Vec2::new(0, 0), 3, 'a');
+    index.insert(Vec2::new(1, 1), 1, 'c');
+    index.insert(Vec2::new(3, 2), 2, 'd');
+    index
+}
+
+#[test]
+fn query_finds_spans_touching_the_box() {
+    let index = index();
+    let around = |x: i64, y: i64| Bounds::new(Vec2::new(x - 1, y - 1), Vec2::new(x + 1, y + 1));
+
+    assert_eq!(values(index.query(around(3, 1))), vec!['a', 'd']);
+    // Only the last cell of `b` is in range
+    assert_eq!(values(index.query(around(15, 1))), vec!['b']);
+    assert_eq!(values(index.query(around(16, 1))), Vec::<char>::new());
+    assert_eq!(values(index.query(around(1, 0))), vec!['a', 'c']);
+}
+
+#[test]
+fn get_returns_the_covering_span() {
+    let index = index();
+    assert_eq!(index.get(Vec2::new(9, 0)).map(|span| span.value), Some('b'));
+    assert_eq!(index.get(Vec2::new(4, 0)), None);
+    assert_eq!(index.get(Vec2::new(0, 3)), None);
+}
+
+#[test]
+fn insert_replaces_overlapping_spans() {
+    let mut index = index();
+    let removed = index.insert(Vec2::new(2, 0), 4, 'e');
+    assert_eq!(values(removed.iter()), vec!['a', 'b']);
+    assert_eq!(values(index.iter()), vec!['e', 'c', 'd']);
+    assert_eq!(index.len(), 3);
+}
+
+#[test]
+fn remove_by_any_cell() {
+    let mut index = index();
+    assert_eq!(
+        index.remove(Vec2::new(4, 2)).map(|span| span.value),
+        Some('d')
+    );
+    assert_eq!(index.remove(Vec2::new(4, 2)), None);
+    assert_eq!(values(index.iter()), vec!['a', 'b', 'c']);
+    assert_eq!(index.len(), 3);
+}
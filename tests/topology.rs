@@ -0,0 +1,77 @@
+use aoc_2023::{
+    engine::{Schematic, SchematicConfig, Vec2},
+    grid::Grid,
+    topology::Topology,
+};
+
+#[test]
+fn resolving_positions() {
+    let resolve = |topology: Topology, x, y| topology.resolve(Vec2::new(x, y), 4, 3);
+
+    assert_eq!(resolve(Topology::Bounded, 3, 2), Some(Vec2::new(3, 2)));
+    assert_eq!(resolve(Topology::Bounded, -1, 0), None);
+    assert_eq!(resolve(Topology::WrapX, -1, 0), Some(Vec2::new(3, 0)));
+    assert_eq!(resolve(Topology::WrapX, 4, -1), None);
+    assert_eq!(resolve(Topology::Torus, 4, -1), Some(Vec2::new(0, 2)));
+    assert_eq!(resolve(Topology::Reflect, -1, 3), Some(Vec2::new(1, 1)));
+    assert_eq!(resolve(Topology::Reflect, 4, 0), Some(Vec2::new(2, 0)));
+    assert_eq!(resolve(Topology::Reflect, 7, 0), Some(Vec2::new(1, 0)));
+    assert_eq!(Topology::Torus.resolve(Vec2::new(5, 5), 0, 0), None);
+}
+
+#[test]
+fn grid_neighbours_wrap() {
+    let grid = Grid::parse(["abc", "def", "ghi"].into_iter(), ' ');
+    let neighbours = |topology| {
+        let mut cells: Vec<char> = grid
+            .neighbours_on(
+                Vec2::new(0, 0),
+                &aoc_2023::geometry::Direction::ALL,
+                topology,
+            )
+            .map(|(_, c)| *c)
+            .collect();
+        cells.sort();
+        cells
+    };
+
+    assert_eq!(neighbours(Topology::Bounded), vec!['b', 'd', 'e']);
+    assert_eq!(neighbours(Topology::WrapX), vec!['b', 'c', 'd', 'e', 'f']);
+    assert_eq!(
+        neighbours(Topology::Torus),
+        vec!['b', 'c', 'd', 'e', 'f', 'g', 'h', 'i']
+    );
+    assert_eq!(
+        neighbours(Topology::Reflect),
+        vec!['b', 'b', 'd', 'd', 'e', 'e', 'e', 'e']
+    );
+}
+
+#[test]
+fn schematic_adjacency_across_edges() {
+    let lines = ["12...*", "......", "..#..7"];
+    let parse = |topology| {
+        let config = SchematicConfig::default().topology(topology);
+        let schematic = Schematic::parse_with(lines.into_iter(), &config).unwrap();
+        let mut parts = schematic.get_valid_part_numbers();
+        parts.sort();
+        (parts, schematic)
+    };
+
+    assert_eq!(parse(Topology::Bounded).0, Vec::<u64>::new());
+    assert_eq!(parse(Topology::WrapX).0, vec![12]);
+    let (parts, torus) = parse(Topology::Torus);
+    assert_eq!(parts, vec![7, 12]);
+    assert_eq!(torus.get_gears()[0].value, Some(84));
+
+    // Edits find neighbours across the edges too
+    let (_, mut edited) = parse(Topology::Torus);
+    edited.set(Vec2::new(5, 0), '.').unwrap();
+    edited.set(Vec2::new(0, 2), '3').unwrap();
+    let mut edited_lines = lines.map(String::from);
+    edited_lines[0].replace_range(5..6, ".");
+    edited_lines[2].replace_range(0..1, "3");
+    let config = SchematicConfig::default().topology(Topology::Torus);
+    let reparsed = Schematic::parse_with(edited_lines.iter().map(|l| l.as_str()), &config).unwrap();
+    assert_eq!(edited, reparsed);
+}
@@ -0,0 +1,52 @@
+use aoc_2023::{
+    geometry::{Bounds, Vec2},
+    grid::Grid,
+    transform::Transform,
+};
+
+fn grid(rows: &[&str]) -> Grid<char> {
+    Grid::parse(rows.iter().copied(), ' ')
+}
+
+#[test]
+fn transformed_grids() {
+    let g = grid(&["abc", "def"]);
+    assert_eq!(g.transpose(), grid(&["ad", "be", "cf"]));
+    assert_eq!(g.rotate_90(), grid(&["da", "eb", "fc"]));
+    assert_eq!(g.rotate_180(), grid(&["fed", "cba"]));
+    assert_eq!(g.rotate_270(), grid(&["cf", "be", "ad"]));
+    assert_eq!(g.flip_horizontal(), grid(&["cba", "fed"]));
+    assert_eq!(g.flip_vertical(), grid(&["def", "abc"]));
+    assert_eq!(
+        g.transformed(Transform::AntiTranspose),
+        grid(&["fc", "eb", "da"])
+    );
+}
+
+#[test]
+fn views_match_copies() {
+    let g = grid(&["abcd", "efgh", "ijkl"]);
+    for transform in Transform::ALL {
+        let view = g.view(transform);
+        let copy = g.transformed(transform);
+        assert_eq!((view.width(), view.height()), (copy.width(), copy.height()));
+        assert_eq!(view.to_string(), copy.to_string(), "{transform:?}");
+        assert_eq!(view.get(Vec2::new(-1, 0)), None);
+    }
+    assert_eq!(g.rotate_90().rotate_90(), g.rotate_180());
+    assert_eq!(g.transpose().transpose(), g);
+}
+
+#[test]
+fn tiling_wraps_in_every_direction() {
+    let g = grid(&["ab", "cd"]);
+    let tiled = g.tiled();
+    assert_eq!(tiled[Vec2::new(-1, -1)], 'd');
+    assert_eq!(tiled[Vec2::new(4, 3)], 'c');
+    assert_eq!(tiled.tile(Vec2::new(-1, 2)), Vec2::new(-1, 1));
+    assert_eq!(tiled.neighbours4(Vec2::new(0, 0)).count(), 4);
+    assert_eq!(
+        tiled.window(Bounds::new(Vec2::new(-1, 0), Vec2::new(2, 0))),
+        grid(&["baba"])
+    );
+}
@@ -0,0 +1,92 @@
+use aoc_2023::engine::{Direction, Vec2};
+
+#[test]
+fn scalar_mul_and_neg() {
+    let mut v = Vec2::new(2, -3);
+    assert_eq!(v * 3, Vec2::new(6, -9));
+    assert_eq!(-v, Vec2::new(-2, 3));
+
+    v *= -2;
+    assert_eq!(v, Vec2::new(-4, 6));
+}
+
+#[test]
+fn distances() {
+    let a = Vec2::new(1, 2);
+    let b = Vec2::new(-3, 7);
+    assert_eq!(a.manhattan(b), 9);
+    assert_eq!(b.manhattan(a), 9);
+    assert_eq!(a.chebyshev(b), 5);
+    assert_eq!(a.manhattan(a), 0);
+    assert_eq!(a.chebyshev(a), 0);
+}
+
+#[test]
+fn rotations() {
+    let east = Vec2::new(1, 0);
+    assert_eq!(east.rotate_right(), Vec2::new(0, 1));
+    assert_eq!(east.rotate_left(), Vec2::new(0, -1));
+
+    let v = Vec2::new(3, -5);
+    assert_eq!(v.rotate_left().rotate_right(), v);
+    assert_eq!(v.rotate_right().rotate_right(), -v);
+    assert_eq!(
+        v.rotate_right()
+            .rotate_right()
+            .rotate_right()
+            .rotate_right(),
+        v
+    );
+}
+
+#[test]
+fn signum() {
+    assert_eq!(Vec2::new(-7, 0).signum(), Vec2::new(-1, 0));
+    assert_eq!(Vec2::new(4, -2).signum(), Vec2::new(1, -1));
+    assert_eq!(Vec2::ZERO.signum(), Vec2::ZERO);
+}
+
+#[test]
+fn tuple_conversions() {
+    let v: Vec2 = (4, -1).into();
+    assert_eq!(v, Vec2::new(4, -1));
+
+    let t: (i64, i64) = v.into();
+    assert_eq!(t, (4, -1));
+}
+
+#[test]
+fn direction_offsets() {
+    assert_eq!(Direction::N.offset(), Vec2::new(0, -1));
+    assert_eq!(Direction::SE.offset(), Vec2::new(1, 1));
+    assert_eq!(Vec2::from(Direction::W), Vec2::new(-1, 0));
+
+    for dir in Direction::ALL {
+        assert_eq!(dir.offset().chebyshev(Vec2::ZERO), 1);
+        assert_eq!(dir.offset() + dir.opposite().offset(), Vec2::ZERO);
+    }
+    for dir in Direction::CARDINAL {
+        assert!(dir.is_cardinal());
+        assert_eq!(dir.offset().manhattan(Vec2::ZERO), 1);
+    }
+}
+
+#[test]
+fn direction_turns() {
+    assert_eq!(Direction::N.turn_right(), Direction::E);
+    assert_eq!(Direction::N.turn_left(), Direction::W);
+    assert_eq!(Direction::NE.turn_right(), Direction::SE);
+    assert_eq!(Direction::N.turn(1), Direction::NE);
+    assert_eq!(Direction::N.turn(-1), Direction::NW);
+    assert_eq!(Direction::W.turn(11), Direction::NE);
+    assert_eq!(Direction::S.opposite(), Direction::N);
+    assert_eq!(Direction::SW.opposite(), Direction::NE);
+}
+
+#[test]
+fn turns_match_vector_rotations() {
+    for dir in Direction::ALL {
+        assert_eq!(dir.turn_right().offset(), dir.offset().rotate_right());
+        assert_eq!(dir.turn_left().offset(), dir.offset().rotate_left());
+    }
+}
@@ -0,0 +1,55 @@
+use aoc_2023::geometry::{Bounds, Vec2, Vec3, Vec4, VecN};
+
+#[test]
+fn operators() {
+    let a = Vec3::new(1, -2, 3);
+    let b = Vec3::new(4, 5, -6);
+    assert_eq!(a + b, Vec3::new(5, 3, -3));
+    assert_eq!(a - b, Vec3::new(-3, -7, 9));
+    assert_eq!(a * 2, Vec3::new(2, -4, 6));
+    assert_eq!(-a, Vec3::new(-1, 2, -3));
+    assert_eq!(a.manhattan(b), 19);
+    assert_eq!(a.chebyshev(b), 9);
+    assert_eq!(b.signum(), Vec3::new(1, 1, -1));
+    assert_eq!(Vec3::from((1, -2, 3)), a);
+}
+
+#[test]
+fn neighbours() {
+    let origin = Vec3::ZERO;
+    assert_eq!(origin.neighbours().count(), 26);
+    assert!(origin.neighbours().all(|n| n.chebyshev(origin) == 1));
+    assert_eq!(origin.orthogonal_neighbours().count(), 6);
+    assert!(origin
+        .orthogonal_neighbours()
+        .all(|n| n.manhattan(origin) == 1));
+
+    assert_eq!(Vec4::ZERO.neighbours().count(), 80);
+    assert_eq!(VecN([3, 3]).neighbours().count(), 8);
+}
+
+#[test]
+fn display() {
+    assert_eq!(Vec3::new(1, -2, 3).to_string(), "[1, -2, 3]");
+    assert_eq!(
+        Vec2::from(VecN([4, 5])).to_string(),
+        VecN([4, 5]).to_string()
+    );
+}
+
+#[test]
+fn bounding_boxes() {
+    let points = [Vec3::new(1, 5, -2), Vec3::new(-3, 2, 0), Vec3::new(0, 7, 4)];
+    let bounds = Bounds::from_points(points).unwrap();
+    assert_eq!(bounds.min, Vec3::new(-3, 2, -2));
+    assert_eq!(bounds.max, Vec3::new(1, 7, 4));
+    assert_eq!(bounds.volume(), 5 * 6 * 7);
+    assert!(points.iter().all(|p| bounds.contains(*p)));
+    assert!(!bounds.contains(Vec3::new(2, 5, 0)));
+    assert_eq!(Bounds::<Vec3>::from_points([]), None);
+
+    let flat = Bounds::new(Vec2::new(2, 3), Vec2::new(0, 1));
+    assert_eq!((flat.width(), flat.height()), (3, 3));
+    assert_eq!(flat.cells().count(), 9);
+    assert_eq!(flat.to_string(), "[0, 1]..=[2, 3]");
+}